@@ -331,7 +331,7 @@ mod tests {
                 #[cfg(not(feature = "quirk_crlf_relaxed"))]
                 Err(ImapClientCodecError::Framing(FramingError::NotCrLf)),
                 #[cfg(feature = "quirk_crlf_relaxed")]
-                Ok(Some(Event::Response(Response::Data(Data::Search(vec![
+                Ok(Some(Event::Response(Response::Data(Data::search(vec![
                     NonZeroU32::try_from(1).unwrap(),
                 ]))))),
             ),
@@ -2,12 +2,20 @@ use std::num::NonZeroU32;
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use imap_codec::{decode::Decoder, encode::Encoder, imap_types::response::Response, ResponseCodec};
-use imap_types::{core::Vec1, fetch::MessageDataItem, response::Data};
+use imap_types::{
+    core::Vec1,
+    fetch::MessageDataItem,
+    response::{Capability, Data},
+};
 
 fn criterion_benchmark(c: &mut Criterion) {
     // # Setup
     let codec = ResponseCodec::new();
-    let instances = [("simple", create_simple()), ("complex", create_complex())];
+    let instances = [
+        ("simple", create_simple()),
+        ("complex", create_complex()),
+        ("capability", create_capability()),
+    ];
 
     for (instance, object) in instances {
         c.bench_function(
@@ -39,6 +47,62 @@ fn create_complex() -> Response<'static> {
     })
 }
 
+/// A capability-heavy response as advertised by large servers.
+fn create_capability() -> Response<'static> {
+    let capabilities = [
+        "IMAP4rev1",
+        "IMAP4rev2",
+        "LITERAL+",
+        "LITERAL-",
+        "SASL-IR",
+        "LOGIN-REFERRALS",
+        "ID",
+        "ENABLE",
+        "IDLE",
+        "SORT",
+        "SORT=DISPLAY",
+        "THREAD=REFERENCES",
+        "THREAD=REFS",
+        "THREAD=ORDEREDSUBJECT",
+        "MULTIAPPEND",
+        "URL-PARTIAL",
+        "CATENATE",
+        "UNSELECT",
+        "CHILDREN",
+        "NAMESPACE",
+        "UIDPLUS",
+        "LIST-EXTENDED",
+        "I18NLEVEL=1",
+        "CONDSTORE",
+        "QRESYNC",
+        "ESEARCH",
+        "ESORT",
+        "SEARCHRES",
+        "WITHIN",
+        "CONTEXT=SEARCH",
+        "LIST-STATUS",
+        "BINARY",
+        "MOVE",
+        "SNIPPET=FUZZY",
+        "PREVIEW=FUZZY",
+        "PREVIEW",
+        "STATUS=SIZE",
+        "SAVEDATE",
+        "QUOTA",
+        "AUTH=PLAIN",
+    ];
+
+    Response::Data(Data::Capability(
+        Vec1::try_from(
+            capabilities
+                .into_iter()
+                .map(|capability| Capability::try_from(capability).unwrap())
+                .collect::<Vec<_>>(),
+        )
+        .unwrap(),
+    ))
+}
+
 #[inline]
 fn serialize(codec: &ResponseCodec, object: &Response) -> Vec<u8> {
     codec.encode(object).dump()
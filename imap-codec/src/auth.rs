@@ -45,6 +45,8 @@ pub(crate) fn authenticate_data(input: &[u8]) -> IMAPResult<&[u8], AuthenticateD
 
 #[cfg(test)]
 mod tests {
+    use imap_types::command::CommandBody;
+
     use super::*;
     use crate::testing::{known_answer_test_encode, known_answer_test_parse};
 
@@ -54,6 +56,8 @@ mod tests {
             (AuthMechanism::Plain, b"PLAIN".as_ref()),
             (AuthMechanism::Login, b"LOGIN"),
             (AuthMechanism::OAuthBearer, b"OAUTHBEARER"),
+            (AuthMechanism::CramMd5, b"CRAM-MD5"),
+            (AuthMechanism::GssApi, b"GSSAPI"),
             (AuthMechanism::try_from("PLAINX").unwrap(), b"PLAINX"),
             (AuthMechanism::try_from("LOGINX").unwrap(), b"LOGINX"),
             (AuthMechanism::try_from("XOAUTH2X").unwrap(), b"XOAUTH2X"),
@@ -64,6 +68,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_encode_authenticate_oauth() {
+        let tests = [
+            (
+                CommandBody::authenticate_with_ir(
+                    AuthMechanism::XOAuth2,
+                    AuthMechanism::xoauth2_initial_response(
+                        "someuser@example.com",
+                        "ya29.vF9dft4qmTc2Nvb3RlckBhdHRhdmlzdGEuY29tCg",
+                    ),
+                ),
+                b"AUTHENTICATE XOAUTH2 dXNlcj1zb21ldXNlckBleGFtcGxlLmNvbQFhdXRoPUJlYXJlciB5YTI5LnZGOWRmdDRxbVRjMk52YjNSbGNrQmhkSFJoZG1semRHRXVZMjl0Q2cBAQ==".as_ref(),
+            ),
+            (
+                CommandBody::authenticate_with_ir(
+                    AuthMechanism::OAuthBearer,
+                    AuthMechanism::oauthbearer_initial_response(
+                        "user@example.com",
+                        "server.example.com",
+                        143,
+                        "vF9dft4qmTc2Nvb3RlckBhbHRhdmlzdGEuY29tCg==",
+                    ),
+                ),
+                b"AUTHENTICATE OAUTHBEARER bixhPXVzZXJAZXhhbXBsZS5jb20sAWhvc3Q9c2VydmVyLmV4YW1wbGUuY29tAXBvcnQ9MTQzAWF1dGg9QmVhcmVyIHZGOWRmdDRxbVRjMk52YjNSbGNrQmhiSFJoZG1semRHRXVZMjl0Q2c9PQEB",
+            ),
+        ];
+
+        for test in tests {
+            known_answer_test_encode(test);
+        }
+    }
+
     #[test]
     fn test_parse_auth_type() {
         let tests = [
@@ -91,6 +127,9 @@ mod tests {
             (b"oAuThbearEr ", b" ", AuthMechanism::OAuthBearer),
             (b"xoauth2 ".as_ref(), b" ".as_ref(), AuthMechanism::XOAuth2),
             (b"xOauTh2 ", b" ", AuthMechanism::XOAuth2),
+            (b"cram-md5 ", b" ", AuthMechanism::CramMd5),
+            (b"Digest-MD5 ", b" ", AuthMechanism::DigestMd5),
+            (b"scram-sha3-512 ", b" ", AuthMechanism::ScramSha3_512),
         ];
 
         for test in tests {
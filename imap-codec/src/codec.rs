@@ -1,34 +1,55 @@
+pub mod client;
+pub mod collect;
 pub mod decode;
 pub mod encode;
+pub mod parse;
+pub mod server;
+pub mod stream;
+pub mod transmit;
+
+/// Default for the maximum length of a line, see, e.g., [`ResponseCodec::with_max_line_length`].
+///
+/// This is generous enough for, e.g., a `SEARCH` response with ~100,000 UIDs.
+pub const DEFAULT_MAX_LINE_LENGTH: usize = 1024 * 1024;
 
 /// Codec for greetings.
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 // We use `#[non_exhaustive]` to prevent users from using struct literal syntax.
 //
 // This allows to add configuration options later. For example, the
 // codec could transparently replace all literals with non-sync literals.
 #[non_exhaustive]
-pub struct GreetingCodec;
+pub struct GreetingCodec {
+    max_line_length: usize,
+}
 
 /// Codec for commands.
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 #[non_exhaustive]
-pub struct CommandCodec;
+pub struct CommandCodec {
+    max_line_length: usize,
+}
 
 /// Codec for authenticate data lines.
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 #[non_exhaustive]
-pub struct AuthenticateDataCodec;
+pub struct AuthenticateDataCodec {
+    max_line_length: usize,
+}
 
 /// Codec for responses.
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 #[non_exhaustive]
-pub struct ResponseCodec;
+pub struct ResponseCodec {
+    max_line_length: usize,
+}
 
 /// Codec for idle dones.
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 #[non_exhaustive]
-pub struct IdleDoneCodec;
+pub struct IdleDoneCodec {
+    max_line_length: usize,
+}
 
 macro_rules! impl_codec_new {
     ($codec:ty) => {
@@ -37,6 +58,28 @@ macro_rules! impl_codec_new {
             pub fn new() -> Self {
                 Self::default()
             }
+
+            /// Set the maximum length of a line (excluding literal data).
+            ///
+            /// Decoding fails when an incomplete line exceeds this limit. This prevents a peer from
+            /// making us buffer an endless line without CRLF. Defaults to [`DEFAULT_MAX_LINE_LENGTH`].
+            pub fn with_max_line_length(mut self, max_line_length: usize) -> Self {
+                self.max_line_length = max_line_length;
+                self
+            }
+
+            /// Get the maximum length of a line (excluding literal data).
+            pub fn max_line_length(&self) -> usize {
+                self.max_line_length
+            }
+        }
+
+        impl Default for $codec {
+            fn default() -> Self {
+                Self {
+                    max_line_length: DEFAULT_MAX_LINE_LENGTH,
+                }
+            }
         }
     };
 }
@@ -107,24 +150,12 @@ mod tests {
             (
                 b"a SeLECT {5}\r\ninbox\r\n",
                 b"",
-                Command::new(
-                    "a",
-                    CommandBody::Select {
-                        mailbox: Mailbox::Inbox,
-                    },
-                )
-                .unwrap(),
+                Command::new("a", CommandBody::select(Mailbox::Inbox).unwrap()).unwrap(),
             ),
             (
                 b"a SElECT {5}\r\ninbox\r\nxxx",
                 b"xxx",
-                Command::new(
-                    "a",
-                    CommandBody::Select {
-                        mailbox: Mailbox::Inbox,
-                    },
-                )
-                .unwrap(),
+                Command::new("a", CommandBody::select(Mailbox::Inbox).unwrap()).unwrap(),
             ),
         ]);
     }
@@ -135,12 +166,12 @@ mod tests {
             (
                 b"* SEARCH 1\r\n".as_ref(),
                 b"".as_ref(),
-                Response::Data(Data::Search(vec![NonZeroU32::new(1).unwrap()])),
+                Response::Data(Data::search(vec![NonZeroU32::new(1).unwrap()])),
             ),
             (
                 b"* SEARCH 1\r\n???",
                 b"???",
-                Response::Data(Data::Search(vec![NonZeroU32::new(1).unwrap()])),
+                Response::Data(Data::search(vec![NonZeroU32::new(1).unwrap()])),
             ),
             (
                 b"* 1 FETCH (RFC822 {5}\r\nhello)\r\n",
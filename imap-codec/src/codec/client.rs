@@ -0,0 +1,65 @@
+//! # Client-side codec functions.
+//!
+//! A client decodes greetings and responses, and encodes commands (including authenticate data
+//! and the `DONE` of `IDLE`). This module only exposes functions for this direction so that the
+//! server-side functions can't be used by accident. See [`server`](crate::server) for the
+//! other direction.
+//!
+//! All functions are thin wrappers around the codecs using their default configuration.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use imap_codec::{
+//!     client,
+//!     imap_types::{
+//!         command::{Command, CommandBody},
+//!         response::{Greeting, GreetingKind},
+//!     },
+//! };
+//!
+//! let (remaining, greeting) = client::decode_greeting(b"* OK ready\r\n").unwrap();
+//! assert_eq!(greeting.kind, GreetingKind::Ok);
+//! assert!(remaining.is_empty());
+//!
+//! let command = Command::new("A1", CommandBody::Noop).unwrap();
+//! assert_eq!(client::encode_command(&command).dump(), b"A1 NOOP\r\n");
+//! ```
+
+use imap_types::{
+    auth::AuthenticateData,
+    command::Command,
+    extensions::idle::IdleDone,
+    response::{Greeting, Response},
+};
+
+use crate::{
+    decode::{Decoder, GreetingDecodeError, ResponseDecodeError},
+    encode::{Encoded, Encoder},
+    AuthenticateDataCodec, CommandCodec, GreetingCodec, IdleDoneCodec, ResponseCodec,
+};
+
+/// Decode a greeting.
+pub fn decode_greeting(input: &[u8]) -> Result<(&[u8], Greeting), GreetingDecodeError> {
+    GreetingCodec::default().decode(input)
+}
+
+/// Decode a response.
+pub fn decode_response(input: &[u8]) -> Result<(&[u8], Response), ResponseDecodeError> {
+    ResponseCodec::default().decode(input)
+}
+
+/// Encode a command.
+pub fn encode_command(command: &Command) -> Encoded {
+    CommandCodec::default().encode(command)
+}
+
+/// Encode authenticate data.
+pub fn encode_authenticate_data(authenticate_data: &AuthenticateData) -> Encoded {
+    AuthenticateDataCodec::default().encode(authenticate_data)
+}
+
+/// Encode the `DONE` ending an `IDLE`.
+pub fn encode_idle_done(idle_done: &IdleDone) -> Encoded {
+    IdleDoneCodec::default().encode(idle_done)
+}
@@ -0,0 +1,231 @@
+//! # Correlation of responses with a command.
+//!
+//! A client sends a tagged command, e.g., `A1 SEARCH UNSEEN`, and receives (zero or more) untagged
+//! data responses followed by a tagged status response completing the command.
+//! The [`ResponseCollector`] accumulates the data responses until the tagged status with the
+//! command's tag arrives.
+//!
+//! Everything else, i.e., untagged status responses (including `BYE`), command continuation
+//! requests, and status responses with another tag, is passed through as
+//! [`Collected::Unsolicited`] and should be handled by the caller.
+//!
+//! Note: IMAP doesn't tell which command an untagged data response belongs to. Thus, all data
+//! responses received while the command is in progress are collected, including, e.g., an
+//! unsolicited `* 3 EXISTS`.
+//!
+//! ## Polling for updates
+//!
+//! `NOOP` (and, to a lesser extent, `CHECK`) has no specific responses. Servers use it to send
+//! pending updates, e.g., `* 23 EXISTS` or `* 14 FETCH (FLAGS (\Seen))`, before the tagged `OK`.
+//! Thus, collecting the responses of a `NOOP` command is the way to pull these updates, e.g., into
+//! a [`MailboxState`](imap_types::state::MailboxState).
+//!
+//! ## Example
+//!
+//! ```rust
+//! use imap_codec::{
+//!     collect::{Collected, ResponseCollector},
+//!     decode::Decoder,
+//!     imap_types::{core::Tag, response::Data},
+//!     ResponseCodec,
+//! };
+//!
+//! let mut collector = ResponseCollector::new(Tag::try_from("A1").unwrap());
+//! let mut input: &[u8] =
+//!     b"* SEARCH 2 3\r\n* OK [ALERT] Maintenance\r\nA1 OK SEARCH completed\r\n";
+//!
+//! loop {
+//!     let (remaining, response) = ResponseCodec::default().decode(input).unwrap();
+//!     input = remaining;
+//!
+//!     match collector.feed(response) {
+//!         Collected::Pending => {}
+//!         Collected::Unsolicited(response) => println!("unsolicited: {response:?}"),
+//!         Collected::Done(status, data) => {
+//!             assert!(status.is_tagged());
+//!             assert!(matches!(data.as_slice(), [Data::Search { .. }]));
+//!             break;
+//!         }
+//!     }
+//! }
+//! ```
+
+use imap_types::{
+    core::Tag,
+    response::{Data, Response, Status},
+};
+
+/// Collects the data responses of a single command.
+///
+/// See the [module documentation](self) for details.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResponseCollector<'a> {
+    tag: Tag<'a>,
+    data: Vec<Data<'a>>,
+}
+
+/// Result of [`ResponseCollector::feed`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Collected<'a> {
+    /// The response was collected, and the command is still in progress.
+    Pending,
+    /// The response is unrelated to the command and was passed through.
+    Unsolicited(Response<'a>),
+    /// The command was completed by a tagged status. Contains all collected data responses.
+    Done(Status<'a>, Vec<Data<'a>>),
+}
+
+impl<'a> ResponseCollector<'a> {
+    /// Create a collector for the command with the given `tag`.
+    pub fn new(tag: Tag<'a>) -> Self {
+        Self {
+            tag,
+            data: Vec::new(),
+        }
+    }
+
+    /// Returns the tag of the command.
+    pub fn tag(&self) -> &Tag<'a> {
+        &self.tag
+    }
+
+    /// Feed the next response received from the server.
+    ///
+    /// After [`Collected::Done`] was returned, the collected data was handed out and the collector
+    /// starts over.
+    pub fn feed(&mut self, response: Response<'a>) -> Collected<'a> {
+        match response {
+            Response::Data(data) => {
+                self.data.push(data);
+                Collected::Pending
+            }
+            Response::Status(status) if status.tag() == Some(&self.tag) => {
+                Collected::Done(status, std::mem::take(&mut self.data))
+            }
+            response => Collected::Unsolicited(response),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use imap_types::state::MailboxState;
+
+    use super::*;
+    use crate::{decode::Decoder, ResponseCodec};
+
+    #[test]
+    fn test_collect_interleaved_responses() {
+        let mut input: &[u8] = b"* 1 FETCH (FLAGS (\\Seen))\r\n\
+* OK [ALERT] Maintenance at noon\r\n\
+* 5 EXISTS\r\n\
++ Ready\r\n\
+B2 OK NOOP completed\r\n\
+* 2 FETCH (FLAGS ())\r\n\
+A1 OK FETCH completed\r\n\
+* BYE Shutting down\r\n";
+
+        let mut collector = ResponseCollector::new(Tag::try_from("A1").unwrap());
+        let mut unsolicited = Vec::new();
+        let mut done = None;
+
+        while !input.is_empty() {
+            let (remaining, response) = ResponseCodec::default().decode(input).unwrap();
+            input = remaining;
+
+            match collector.feed(response) {
+                Collected::Pending => {}
+                Collected::Unsolicited(response) => unsolicited.push(response),
+                Collected::Done(status, data) => done = Some((status, data)),
+            }
+        }
+
+        let (status, data) = done.unwrap();
+        assert_eq!(status.tag(), Some(collector.tag()));
+        assert_eq!(
+            data.iter()
+                .map(|data| match data {
+                    Data::Fetch { seq, .. } => *seq,
+                    Data::Exists(exists) => NonZeroU32::new(*exists).unwrap(),
+                    _ => unreachable!(),
+                })
+                .collect::<Vec<_>>(),
+            [1, 5, 2].map(|seq| NonZeroU32::new(seq).unwrap())
+        );
+
+        assert_eq!(unsolicited.len(), 4);
+        assert!(matches!(
+            unsolicited[0],
+            Response::Status(Status::Untagged(_))
+        ));
+        assert!(matches!(
+            unsolicited[1],
+            Response::CommandContinuationRequest(_)
+        ));
+        assert!(matches!(
+            &unsolicited[2],
+            Response::Status(status) if status.tag() == Some(&Tag::try_from("B2").unwrap())
+        ));
+        assert!(matches!(unsolicited[3], Response::Status(Status::Bye(_))));
+    }
+
+    #[test]
+    fn test_collect_noop_updates() {
+        // Example from RFC 3501, section 6.1.2.
+        let mut input: &[u8] = b"* 22 EXPUNGE\r\n\
+* 23 EXISTS\r\n\
+* 3 RECENT\r\n\
+* 14 FETCH (FLAGS (\\Seen \\Deleted))\r\n\
+a047 OK NOOP completed\r\n";
+
+        let mut collector = ResponseCollector::new(Tag::try_from("a047").unwrap());
+        let mut state = MailboxState::default();
+
+        let data = loop {
+            let (remaining, response) = ResponseCodec::default().decode(input).unwrap();
+            input = remaining;
+
+            match collector.feed(response) {
+                Collected::Pending => {}
+                Collected::Unsolicited(response) => panic!("unexpected {response:?}"),
+                Collected::Done(status, data) => {
+                    assert_eq!(status.tag(), Some(collector.tag()));
+                    break data;
+                }
+            }
+        };
+
+        assert!(input.is_empty());
+        assert_eq!(data.len(), 4);
+        assert_eq!(data[0], Data::Expunge(NonZeroU32::new(22).unwrap()));
+        assert!(matches!(&data[3], Data::Fetch { seq, .. } if seq.get() == 14));
+
+        for data in data {
+            state.update(&Response::Data(data));
+        }
+        assert_eq!(state.exists(), Some(23));
+        assert_eq!(state.recent(), Some(3));
+    }
+
+    #[test]
+    fn test_collect_starts_over() {
+        let mut collector = ResponseCollector::new(Tag::try_from("A1").unwrap());
+
+        assert_eq!(
+            collector.feed(Response::Data(Data::Exists(1))),
+            Collected::Pending
+        );
+
+        let status = Status::ok(Some(Tag::try_from("A1").unwrap()), None, "done").unwrap();
+        assert_eq!(
+            collector.feed(Response::Status(status.clone())),
+            Collected::Done(status.clone(), vec![Data::Exists(1)])
+        );
+        assert_eq!(
+            collector.feed(Response::Status(status.clone())),
+            Collected::Done(status, vec![])
+        );
+    }
+}
@@ -8,6 +8,20 @@
 //! This makes real-world decoding of IMAP more elaborate.
 //!
 //! Have a look at the [parse_command](https://github.com/duesee/imap-codec/blob/main/imap-codec/examples/parse_command.rs) example to see how a real-world application could decode IMAP.
+//!
+//! Decoders return the remaining input, so a buffer containing multiple messages is decoded by calling [`Decoder::decode`] repeatedly.
+//! This preserves the order of messages, which matters for `* n EXPUNGE` responses in particular:
+//! Every EXPUNGE immediately decrements the sequence numbers of all subsequent messages, so a client must apply them one after another (e.g., `* 3 EXPUNGE`, `* 3 EXPUNGE` removes the messages that were initially numbered 3 and 4).
+//! The decoder does not reorder or renumber anything, i.e., tracking sequence numbers is up to the application.
+//!
+//! Decoders are stateless and never consume more than a single message.
+//! This is important when the byte stream changes its meaning after a message, e.g., after STARTTLS or COMPRESS:
+//! Once the server sent `A OK [COMPRESSIONACTIVE] ...\r\n` in response to `A COMPRESS DEFLATE`, all subsequent bytes are deflate-compressed.
+//! The remaining input returned by [`Decoder::decode`] is thus the start of the compressed stream and must be decompressed before it is fed to a decoder again.
+//! Similarly, any buffered state, e.g., a [`LiteralStreamer`](crate::stream::LiteralStreamer), must be discarded at this boundary.
+//!
+//! Decoders don't buffer, but a caller buffering input until a message is complete must not do so forever.
+//! Thus, decoding fails when an incomplete line (not counting literal data) exceeds the codec's maximum line length, e.g., [`ResponseCodec::with_max_line_length`](crate::ResponseCodec::with_max_line_length).
 
 use std::num::{ParseIntError, TryFromIntError};
 
@@ -27,6 +41,7 @@ use crate::{
     command::command,
     extensions::idle::idle_done,
     response::{greeting, response},
+    stream::{parse_literal_announcement, LiteralAnnouncement},
     AuthenticateDataCodec, CommandCodec, GreetingCodec, IdleDoneCodec, ResponseCodec,
 };
 
@@ -123,6 +138,167 @@ pub trait Decoder {
         let (remaining, value) = self.decode(input).map_err(IntoBoundedStatic::into_static)?;
         Ok((remaining, value.into_static()))
     }
+
+    /// Decode a message and update `stats`.
+    ///
+    /// Statistics are only updated when a message was decoded successfully.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use imap_codec::{
+    ///     decode::{DecodeStats, Decoder},
+    ///     ResponseCodec,
+    /// };
+    ///
+    /// let mut stats = DecodeStats::default();
+    ///
+    /// let (remaining, _) = ResponseCodec::default()
+    ///     .decode_with_stats(
+    ///         b"* 1 FETCH (BODY[] {5}\r\nHello)\r\n* 1 EXISTS\r\n",
+    ///         &mut stats,
+    ///     )
+    ///     .unwrap();
+    /// ResponseCodec::default()
+    ///     .decode_with_stats(remaining, &mut stats)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(stats.messages, 2);
+    /// assert_eq!(stats.literals, 1);
+    /// ```
+    #[cfg(feature = "decode_stats")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "decode_stats")))]
+    fn decode_with_stats<'a>(
+        &self,
+        input: &'a [u8],
+        stats: &mut DecodeStats,
+    ) -> Result<(&'a [u8], Self::Message<'a>), Self::Error<'a>> {
+        let (remaining, message) = self.decode(input)?;
+
+        stats.record(&input[..input.len() - remaining.len()]);
+
+        Ok((remaining, message))
+    }
+
+    /// Decode a message and return the number of consumed bytes instead of the remaining input.
+    ///
+    /// This is handy when `input` is a read buffer that must be advanced after decoding.
+    /// As with [`Decoder::decode`], an incomplete message is reported as an error and nothing is
+    /// consumed, so the caller should read more data and try again.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use imap_codec::{
+    ///     decode::{Decoder, GreetingDecodeError},
+    ///     GreetingCodec,
+    /// };
+    ///
+    /// let codec = GreetingCodec::default();
+    ///
+    /// // The first read returned only a part of the greeting ...
+    /// assert_eq!(
+    ///     codec.decode_consumed(b"* OK IMAP4rev1 Se"),
+    ///     Err(GreetingDecodeError::Incomplete)
+    /// );
+    ///
+    /// // ... and the second read returned the rest of it (and more).
+    /// let (consumed, greeting) = codec
+    ///     .decode_consumed(b"* OK IMAP4rev1 Server ready\r\n* CAPABILITY")
+    ///     .unwrap();
+    /// assert_eq!(consumed, 29);
+    /// assert_eq!(greeting.text.as_ref(), "IMAP4rev1 Server ready");
+    /// ```
+    fn decode_consumed<'a>(
+        &self,
+        input: &'a [u8],
+    ) -> Result<(usize, Self::Message<'a>), Self::Error<'a>> {
+        let (remaining, message) = self.decode(input)?;
+
+        Ok((input.len() - remaining.len(), message))
+    }
+
+    /// Decode exactly one message from `input`.
+    ///
+    /// In contrast to [`Decoder::decode`], this fails when bytes remain after the message.
+    /// This is useful when `input` is known to contain a single message and catches framing bugs,
+    /// e.g., two messages that were concatenated by accident.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use imap_codec::{
+    ///     decode::{DecodeExactError, Decoder},
+    ///     ResponseCodec,
+    /// };
+    ///
+    /// assert!(ResponseCodec::default()
+    ///     .decode_exact(b"A1 OK done\r\n")
+    ///     .is_ok());
+    /// assert_eq!(
+    ///     ResponseCodec::default().decode_exact(b"A1 OK done\r\nGARBAGE"),
+    ///     Err(DecodeExactError::TrailingBytes {
+    ///         remaining: b"GARBAGE".as_ref()
+    ///     })
+    /// );
+    /// ```
+    fn decode_exact<'a>(
+        &self,
+        input: &'a [u8],
+    ) -> Result<Self::Message<'a>, DecodeExactError<'a, Self::Error<'a>>> {
+        match self.decode(input) {
+            Ok((remaining, _)) if !remaining.is_empty() => {
+                Err(DecodeExactError::TrailingBytes { remaining })
+            }
+            Ok((_, message)) => Ok(message),
+            Err(error) => Err(DecodeExactError::Decode(error)),
+        }
+    }
+}
+
+/// Statistics collected by [`Decoder::decode_with_stats`].
+///
+/// This is meant for profiling, e.g., to identify servers sending pathological messages.
+#[cfg(feature = "decode_stats")]
+#[cfg_attr(docsrs, doc(cfg(feature = "decode_stats")))]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DecodeStats {
+    /// Number of consumed bytes (including literal data).
+    pub bytes_consumed: u64,
+    /// Number of decoded messages.
+    pub messages: u64,
+    /// Number of literals in decoded messages.
+    pub literals: u64,
+    /// Length of the largest literal.
+    pub max_literal_length: u32,
+}
+
+#[cfg(feature = "decode_stats")]
+impl DecodeStats {
+    /// Record a single, completely decoded, message.
+    fn record(&mut self, message: &[u8]) {
+        self.bytes_consumed += message.len() as u64;
+        self.messages += 1;
+
+        // The message was already decoded, so it doesn't need to be parsed again.
+        walk_literals(message, |LiteralAnnouncement { length, .. }| {
+            self.literals += 1;
+            self.max_literal_length = self.max_literal_length.max(length);
+        });
+    }
+}
+
+/// Error produced by [`Decoder::decode_exact`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DecodeExactError<'a, E> {
+    /// Decoding the message failed.
+    Decode(E),
+
+    /// The message was decoded, but unexpected bytes remained.
+    TrailingBytes {
+        /// Bytes following the message.
+        remaining: &'a [u8],
+    },
 }
 
 /// Error during greeting decoding.
@@ -149,6 +325,9 @@ pub enum CommandDecodeError<'a> {
     /// command continuation request to agree to the receival of the remaining data. This behaviour
     /// is different when `LITERAL+/LITERAL-` is used.
     ///
+    /// After sending the continuation request, the server decodes again once more data arrived.
+    /// (A client uses a [`CommandTransmitter`](crate::transmit::CommandTransmitter) for its side.)
+    ///
     /// # With `LITERAL+/LITERAL-`
     ///
     /// When the `mode` is sync, everything is the same as above.
@@ -244,6 +423,107 @@ pub enum IdleDoneDecodeError {
 
 // -------------------------------------------------------------------------------------------------
 
+/// Skip everything up to and including the next line ending.
+///
+/// This can be used to resynchronize after a decoder returned `Failed`, e.g., to keep a (logging)
+/// proxy connected to a slightly broken server. Returns `None` when `input` contains no line
+/// ending yet.
+///
+/// # Warning: Lossy
+///
+/// The skipped bytes are dropped without further inspection. When the malformed line announced a
+/// literal, the literal data is interpreted as the next message(s). Use this as a last resort only.
+///
+/// # Example
+///
+/// ```rust
+/// use imap_codec::{
+///     decode::{recover_to_next_line, Decoder, ResponseDecodeError},
+///     ResponseCodec,
+/// };
+///
+/// let input = b"* MALFORMED (\r\n* OK ...\r\n";
+///
+/// assert_eq!(
+///     ResponseCodec::default().decode(input),
+///     Err(ResponseDecodeError::Failed)
+/// );
+///
+/// let remaining = recover_to_next_line(input).unwrap();
+/// assert!(ResponseCodec::default().decode(remaining).is_ok());
+/// ```
+pub fn recover_to_next_line(input: &[u8]) -> Option<&[u8]> {
+    // Note: Searching for `\n` also covers `quirk_crlf_relaxed`.
+    input
+        .iter()
+        .position(|byte| *byte == b'\n')
+        .map(|position| &input[position + 1..])
+}
+
+/// Returns `true` when the last (incomplete) line in `input` exceeds `max_line_length`.
+///
+/// Literal data doesn't count towards the line length, and `literals` is `false` for messages
+/// that can't contain literals. `input` must be a message prefix the parser didn't reject.
+fn line_too_long(input: &[u8], max_line_length: usize, literals: bool) -> bool {
+    if input.len() <= max_line_length {
+        return false;
+    }
+
+    // Only look at the last bytes first, so that a slowly growing line is not scanned again and
+    // again. When they contain a line break, the last line is short enough.
+    if input[input.len() - max_line_length - 1..].contains(&b'\n') {
+        return false;
+    }
+
+    if !literals {
+        return true;
+    }
+
+    match walk_literals(input, |_| {}) {
+        Some(start) => input.len() - start > max_line_length,
+        None => false,
+    }
+}
+
+/// Walk over the lines of `input` and skip literal data.
+///
+/// `input` must be a complete message, or a message prefix the parser didn't reject. Then, every
+/// line but the last one announces a literal, because `text` (which could end in, e.g., `{5}`)
+/// always ends a message. This makes a (second) parser run unnecessary.
+///
+/// Calls `literal` for every literal and returns the start of the last line, or `None` when
+/// `input` ends in literal data.
+fn walk_literals<F>(input: &[u8], mut literal: F) -> Option<usize>
+where
+    F: FnMut(LiteralAnnouncement),
+{
+    let mut start = 0;
+
+    loop {
+        let end = match input[start..].iter().position(|byte| *byte == b'\n') {
+            Some(position) => start + position + 1,
+            None => return Some(start),
+        };
+
+        if end == input.len() {
+            return Some(end);
+        }
+
+        match parse_literal_announcement(&input[start..end]) {
+            Some((_, announcement)) => {
+                literal(announcement);
+
+                start = end.checked_add(announcement.length as usize)?;
+
+                if start > input.len() {
+                    return None;
+                }
+            }
+            None => start = end,
+        }
+    }
+}
+
 impl Decoder for GreetingCodec {
     type Message<'a> = Greeting<'a>;
     type Error<'a> = GreetingDecodeError;
@@ -254,7 +534,13 @@ impl Decoder for GreetingCodec {
     ) -> Result<(&'a [u8], Self::Message<'a>), Self::Error<'static>> {
         match greeting(input) {
             Ok((rem, grt)) => Ok((rem, grt)),
-            Err(nom::Err::Incomplete(_)) => Err(GreetingDecodeError::Incomplete),
+            Err(nom::Err::Incomplete(_)) => {
+                if line_too_long(input, self.max_line_length, false) {
+                    Err(GreetingDecodeError::Failed)
+                } else {
+                    Err(GreetingDecodeError::Incomplete)
+                }
+            }
             Err(nom::Err::Failure(_)) | Err(nom::Err::Error(_)) => Err(GreetingDecodeError::Failed),
         }
     }
@@ -270,7 +556,13 @@ impl Decoder for CommandCodec {
     ) -> Result<(&'a [u8], Self::Message<'a>), Self::Error<'a>> {
         match command(input) {
             Ok((rem, cmd)) => Ok((rem, cmd)),
-            Err(nom::Err::Incomplete(_)) => Err(CommandDecodeError::Incomplete),
+            Err(nom::Err::Incomplete(_)) => {
+                if line_too_long(input, self.max_line_length, true) {
+                    Err(CommandDecodeError::Failed)
+                } else {
+                    Err(CommandDecodeError::Incomplete)
+                }
+            }
             Err(nom::Err::Failure(error)) => match error {
                 IMAPParseError {
                     input: _,
@@ -298,7 +590,13 @@ impl Decoder for ResponseCodec {
     ) -> Result<(&'a [u8], Self::Message<'a>), Self::Error<'static>> {
         match response(input) {
             Ok((rem, rsp)) => Ok((rem, rsp)),
-            Err(nom::Err::Incomplete(_)) => Err(ResponseDecodeError::Incomplete),
+            Err(nom::Err::Incomplete(_)) => {
+                if line_too_long(input, self.max_line_length, true) {
+                    Err(ResponseDecodeError::Failed)
+                } else {
+                    Err(ResponseDecodeError::Incomplete)
+                }
+            }
             Err(nom::Err::Error(error) | nom::Err::Failure(error)) => match error {
                 IMAPParseError {
                     kind: IMAPErrorKind::Literal { length, .. },
@@ -320,7 +618,13 @@ impl Decoder for AuthenticateDataCodec {
     ) -> Result<(&'a [u8], Self::Message<'a>), Self::Error<'static>> {
         match authenticate_data(input) {
             Ok((rem, rsp)) => Ok((rem, rsp)),
-            Err(nom::Err::Incomplete(_)) => Err(AuthenticateDataDecodeError::Incomplete),
+            Err(nom::Err::Incomplete(_)) => {
+                if line_too_long(input, self.max_line_length, false) {
+                    Err(AuthenticateDataDecodeError::Failed)
+                } else {
+                    Err(AuthenticateDataDecodeError::Incomplete)
+                }
+            }
             Err(nom::Err::Failure(_)) | Err(nom::Err::Error(_)) => {
                 Err(AuthenticateDataDecodeError::Failed)
             }
@@ -338,7 +642,13 @@ impl Decoder for IdleDoneCodec {
     ) -> Result<(&'a [u8], Self::Message<'a>), Self::Error<'static>> {
         match idle_done(input) {
             Ok((rem, rsp)) => Ok((rem, rsp)),
-            Err(nom::Err::Incomplete(_)) => Err(IdleDoneDecodeError::Incomplete),
+            Err(nom::Err::Incomplete(_)) => {
+                if line_too_long(input, self.max_line_length, false) {
+                    Err(IdleDoneDecodeError::Failed)
+                } else {
+                    Err(IdleDoneDecodeError::Incomplete)
+                }
+            }
             Err(nom::Err::Failure(_)) | Err(nom::Err::Error(_)) => Err(IdleDoneDecodeError::Failed),
         }
     }
@@ -354,10 +664,11 @@ mod tests {
         extensions::idle::IdleDone,
         fetch::MessageDataItem,
         mailbox::Mailbox,
-        response::{Data, Greeting, GreetingKind, Response},
+        response::{Capability, Code, Data, Greeting, GreetingKind, Response, Status},
     };
 
     use super::*;
+    use crate::DEFAULT_MAX_LINE_LENGTH;
 
     #[test]
     fn test_decode_greeting() {
@@ -429,26 +740,14 @@ mod tests {
                 b"a select {5}\r\ninbox\r\n".as_ref(),
                 Ok((
                     b"".as_ref(),
-                    Command::new(
-                        "a",
-                        CommandBody::Select {
-                            mailbox: Mailbox::Inbox,
-                        },
-                    )
-                    .unwrap(),
+                    Command::new("a", CommandBody::select(Mailbox::Inbox).unwrap()).unwrap(),
                 )),
             ),
             (
                 b"a select {5}\r\ninbox\r\nxxx".as_ref(),
                 Ok((
                     b"xxx".as_ref(),
-                    Command::new(
-                        "a",
-                        CommandBody::Select {
-                            mailbox: Mailbox::Inbox,
-                        },
-                    )
-                    .unwrap(),
+                    Command::new("a", CommandBody::select(Mailbox::Inbox).unwrap()).unwrap(),
                 )),
             ),
             // Incomplete
@@ -491,6 +790,236 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_decode_command_literal_continuation() {
+        use imap_types::response::CommandContinuationRequest;
+
+        use crate::{encode::Encoder, ResponseCodec};
+
+        let codec = CommandCodec::default();
+        let mut buffer = b"A LOGIN alice {6}\r\n".to_vec();
+        let mut sent = Vec::new();
+
+        // The server stops at the literal and must ask for the password.
+        match codec.decode(&buffer) {
+            Err(CommandDecodeError::LiteralFound { tag, length, mode }) => {
+                assert_eq!(tag, Tag::try_from("A").unwrap());
+                assert_eq!(length, 6);
+                assert_eq!(mode, LiteralMode::Sync);
+
+                let continuation = CommandContinuationRequest::basic(None, "Ready").unwrap();
+                sent.extend(
+                    ResponseCodec::default()
+                        .encode(&Response::CommandContinuationRequest(continuation))
+                        .dump(),
+                );
+            }
+            got => panic!("Expected `LiteralFound`, got {got:?}"),
+        }
+        assert_eq!(sent, b"+ Ready\r\n");
+
+        // The client sends the literal in two parts.
+        buffer.extend_from_slice(b"sec");
+        assert_eq!(codec.decode(&buffer), Err(CommandDecodeError::Incomplete));
+
+        buffer.extend_from_slice(b"ret\r\n");
+        let (remaining, command) = codec.decode(&buffer).unwrap();
+        assert!(remaining.is_empty());
+        assert_eq!(
+            command,
+            Command::new(
+                "A",
+                CommandBody::login("alice", Literal::try_from("secret").unwrap()).unwrap(),
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_decode_exact() {
+        let codec = ResponseCodec::default();
+
+        assert_eq!(
+            codec.decode_exact(b"A1 OK done\r\n"),
+            Ok(Response::Status(
+                Status::ok(Some(Tag::try_from("A1").unwrap()), None, "done").unwrap()
+            ))
+        );
+        assert_eq!(
+            codec.decode_exact(b"A1 OK done\r\nGARBAGE"),
+            Err(DecodeExactError::TrailingBytes {
+                remaining: b"GARBAGE".as_ref()
+            })
+        );
+        assert_eq!(
+            codec.decode_exact(b"A1 OK done\r\n* OK ...\r\n"),
+            Err(DecodeExactError::TrailingBytes {
+                remaining: b"* OK ...\r\n".as_ref()
+            })
+        );
+        assert_eq!(
+            codec.decode_exact(b"A1 OK done"),
+            Err(DecodeExactError::Decode(ResponseDecodeError::Incomplete))
+        );
+        assert_eq!(
+            GreetingCodec::default().decode_exact(b"A1 OK done\r\n"),
+            Err(DecodeExactError::Decode(GreetingDecodeError::Failed))
+        );
+    }
+
+    #[test]
+    fn test_decode_consumed() {
+        let codec = GreetingCodec::default();
+        let greeting = b"* OK [CAPABILITY IMAP4rev1] ready\r\n".as_ref();
+
+        // Every prefix is incomplete.
+        for end in 0..greeting.len() {
+            assert_eq!(
+                codec.decode_consumed(&greeting[..end]),
+                Err(GreetingDecodeError::Incomplete)
+            );
+        }
+
+        let expected = Greeting::new(
+            GreetingKind::Ok,
+            Some(Code::Capability(
+                Vec1::try_from(vec![Capability::Imap4Rev1]).unwrap(),
+            )),
+            "ready",
+        )
+        .unwrap();
+
+        assert_eq!(
+            codec.decode_consumed(greeting),
+            Ok((greeting.len(), expected.clone()))
+        );
+        assert_eq!(
+            codec.decode_consumed(b"* OK [CAPABILITY IMAP4rev1] ready\r\nA1 OK"),
+            Ok((greeting.len(), expected))
+        );
+        assert_eq!(
+            codec.decode_consumed(b"* NO ready\r\n"),
+            Err(GreetingDecodeError::Failed)
+        );
+
+        assert_eq!(
+            ResponseCodec::default().decode_consumed(b"* 1 EXISTS\r\n* 2 EXISTS\r\n"),
+            Ok((12, Response::Data(Data::Exists(1))))
+        );
+    }
+
+    #[cfg(feature = "decode_stats")]
+    #[test]
+    fn test_decode_with_stats() {
+        let codec = ResponseCodec::default();
+        let mut stats = DecodeStats::default();
+
+        let mut input = b"* 1 FETCH (BODY[] {5}\r\nHello BODY[HEADER] {11}\r\nSubject: Hi)\r\n\
+            * 2 EXISTS\r\n\
+            A1 OK done\r\n\
+            A2 NO No such mailbox foo{5}\r\n\
+            * 2 FETCH (BODY[]"
+            .as_ref();
+
+        for _ in 0..4 {
+            (input, _) = codec.decode_with_stats(input, &mut stats).unwrap();
+        }
+
+        // Incomplete messages are not counted.
+        assert_eq!(
+            codec.decode_with_stats(input, &mut stats),
+            Err(ResponseDecodeError::Incomplete)
+        );
+
+        assert_eq!(
+            stats,
+            DecodeStats {
+                bytes_consumed: 116,
+                messages: 4,
+                literals: 2,
+                max_literal_length: 11,
+            }
+        );
+
+        let mut stats = DecodeStats::default();
+        CommandCodec::default()
+            .decode_with_stats(b"A LOGIN {5+}\r\nalice bob\r\n", &mut stats)
+            .unwrap();
+        assert_eq!(stats.literals, 1);
+        assert_eq!(stats.max_literal_length, 5);
+    }
+
+    #[test]
+    fn test_recover_to_next_line() {
+        let codec = ResponseCodec::default();
+        let input = b"A1 OK [UIDNEXT 1 done\r\n* 1 EXISTS\r\nA1 OK done\r\n".as_ref();
+
+        assert_eq!(codec.decode(input), Err(ResponseDecodeError::Failed));
+
+        let remaining = recover_to_next_line(input).unwrap();
+        assert_eq!(remaining, b"* 1 EXISTS\r\nA1 OK done\r\n");
+
+        let (remaining, response) = codec.decode(remaining).unwrap();
+        assert_eq!(response, Response::Data(Data::Exists(1)));
+        assert!(codec.decode_exact(remaining).is_ok());
+
+        assert_eq!(recover_to_next_line(b"* MALFORMED"), None);
+        assert_eq!(recover_to_next_line(b"\r\n"), Some(b"".as_ref()));
+    }
+
+    #[test]
+    fn test_decode_line_too_long() {
+        let mut endless = b"* OK ".to_vec();
+        endless.resize(DEFAULT_MAX_LINE_LENGTH + 1, b'x');
+        assert_eq!(
+            ResponseCodec::default().decode(&endless),
+            Err(ResponseDecodeError::Failed)
+        );
+        assert_eq!(
+            ResponseCodec::default().decode(&endless[..DEFAULT_MAX_LINE_LENGTH]),
+            Err(ResponseDecodeError::Incomplete)
+        );
+
+        let codec = CommandCodec::default().with_max_line_length(16);
+        assert_eq!(codec.max_line_length(), 16);
+        assert_eq!(
+            codec.decode(b"a LOGIN aaaaaaaaaaaaaa"),
+            Err(CommandDecodeError::Failed)
+        );
+        assert_eq!(
+            codec.decode(b"a LOGIN aaaaaaaa"),
+            Err(CommandDecodeError::Incomplete)
+        );
+        // Literal data doesn't count towards the line length ...
+        assert_eq!(
+            codec.decode(b"a LOGIN {20}\r\naaaaaaaaaaaaaaaaaaaa b"),
+            Err(CommandDecodeError::Incomplete)
+        );
+        assert_eq!(
+            codec.decode(b"a LOGIN {20}\r\naaaaaaaaaaaaaaaaaaa"),
+            Err(CommandDecodeError::Incomplete)
+        );
+        assert_eq!(
+            codec.decode(b"a LOGIN {30}\r\na\naaaaaaaaaaaaaaaaaaaaaaaa"),
+            Err(CommandDecodeError::Incomplete)
+        );
+        assert_eq!(
+            codec.decode(b"a LOGIN {20}\r\naaaaaaaa\r\naaaaaaaaaa bbbb"),
+            Err(CommandDecodeError::Incomplete)
+        );
+        // ... but the line after it does.
+        assert_eq!(
+            codec.decode(b"a LOGIN {20}\r\naaaaaaaaaaaaaaaaaaaa bbbbbbbbbbbbbbbb"),
+            Err(CommandDecodeError::Failed)
+        );
+
+        let codec = AuthenticateDataCodec::default().with_max_line_length(4);
+        assert_eq!(
+            codec.decode(b"AAAAAAAA"),
+            Err(AuthenticateDataDecodeError::Failed)
+        );
+    }
+
     #[test]
     fn test_decode_authenticate_data() {
         let tests = [
@@ -637,14 +1166,14 @@ mod tests {
                 b"* SEARCH 1\r\n".as_ref(),
                 Ok((
                     b"".as_ref(),
-                    Response::Data(Data::Search(vec![NonZeroU32::new(1).unwrap()])),
+                    Response::Data(Data::search(vec![NonZeroU32::new(1).unwrap()])),
                 )),
             ),
             (
                 b"* SEARCH 1\r\n???".as_ref(),
                 Ok((
                     b"???".as_ref(),
-                    Response::Data(Data::Search(vec![NonZeroU32::new(1).unwrap()])),
+                    Response::Data(Data::search(vec![NonZeroU32::new(1).unwrap()])),
                 )),
             ),
             (
@@ -663,6 +1192,10 @@ mod tests {
                 b"* 1 FETCH (RFC822 {5}\r\n".as_ref(),
                 Err(ResponseDecodeError::LiteralFound { length: 5 }),
             ),
+            (
+                b"* 1 FETCH (RFC822 {5}\r\nhe\r\n".as_ref(),
+                Err(ResponseDecodeError::Incomplete),
+            ),
             // Failed
             (
                 b"*  search 1 2 3\r\n".as_ref(),
@@ -683,4 +1216,71 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_decode_response_logout() {
+        let input = b"* BYE IMAP4rev1 Server logging out\r\nA023 OK LOGOUT completed\r\n";
+
+        let (remaining, got) = ResponseCodec::default().decode(input).unwrap();
+        assert_eq!(
+            got,
+            Response::Status(Status::bye(None, "IMAP4rev1 Server logging out").unwrap())
+        );
+
+        let (remaining, got) = ResponseCodec::default().decode(remaining).unwrap();
+        assert_eq!(
+            got,
+            Response::Status(
+                Status::ok(
+                    Some(Tag::try_from("A023").unwrap()),
+                    None,
+                    "LOGOUT completed"
+                )
+                .unwrap()
+            )
+        );
+        assert!(remaining.is_empty());
+    }
+
+    #[test]
+    fn test_decode_response_compression_boundary() {
+        // Tagged OK followed by (the start of) a deflate stream.
+        let input = b"A01 OK [COMPRESSIONACTIVE] DEFLATE active\r\n\x78\x9c\xf2\x0d\r\n";
+
+        let (remaining, got) = ResponseCodec::default().decode(input).unwrap();
+        assert_eq!(
+            got,
+            Response::Status(
+                Status::ok(
+                    Some(Tag::try_from("A01").unwrap()),
+                    Some(Code::CompressionActive),
+                    "DEFLATE active"
+                )
+                .unwrap()
+            )
+        );
+        // Compressed data is left untouched.
+        assert_eq!(remaining, b"\x78\x9c\xf2\x0d\r\n");
+    }
+
+    #[test]
+    fn test_decode_response_expunge_in_order() {
+        let input = b"* 3 EXPUNGE\r\n* 3 EXPUNGE\r\n* 1 EXPUNGE\r\n";
+
+        let mut remaining = input.as_ref();
+        let mut got = Vec::new();
+        while !remaining.is_empty() {
+            let (rem, rsp) = ResponseCodec::default().decode(remaining).unwrap();
+            remaining = rem;
+            got.push(rsp);
+        }
+
+        assert_eq!(
+            got,
+            [3, 3, 1]
+                .into_iter()
+                .map(|seq| Response::Data(Data::Expunge(NonZeroU32::new(seq).unwrap())))
+                .collect::<Vec<_>>()
+        );
+    }
 }
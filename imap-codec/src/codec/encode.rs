@@ -81,6 +81,8 @@ use imap_types::{
 };
 use utils::{join_serializable, List1AttributeValueOrNil, List1OrNil};
 
+#[cfg(feature = "ext_gmail")]
+use crate::extensions::gmail::encode_label_list;
 use crate::{AuthenticateDataCodec, CommandCodec, GreetingCodec, IdleDoneCodec, ResponseCodec};
 
 /// Encoder.
@@ -93,6 +95,48 @@ pub trait Encoder {
     ///
     /// This will return an [`Encoded`] message.
     fn encode(&self, message: &Self::Message<'_>) -> Encoded;
+
+    /// Compute the length of the encoded message (including literals) in bytes.
+    ///
+    /// This is equal to `self.encode(message).dump().len()` but doesn't collect the encoded data.
+    /// Useful, e.g., to decide how many commands to pipeline without exceeding a buffer size.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use imap_codec::{
+    ///     encode::Encoder,
+    ///     imap_types::command::{Command, CommandBody},
+    ///     CommandCodec,
+    /// };
+    ///
+    /// let cmd = Command::new(
+    ///     "A",
+    ///     CommandBody::login("alice", b"\xCA\xFE".as_ref()).unwrap(),
+    /// )
+    /// .unwrap();
+    ///
+    /// // "A LOGIN alice {2}\r\n" + "\xCA\xFE" + "\r\n"
+    /// assert_eq!(CommandCodec::default().encoded_len(&cmd), 23);
+    /// ```
+    fn encoded_len(&self, message: &Self::Message<'_>) -> usize {
+        self.encode(message).dump().len()
+    }
+
+    /// Encode this message (including literals) and append it to `dst`.
+    ///
+    /// The codecs of this crate reserve [`Encoder::encoded_len`] bytes in `dst` and write directly
+    /// into it, i.e., no [`Fragment`]s are created and `dst` is reallocated at most once.
+    ///
+    /// The default implementation copies the [`Encoded::dump`] of [`Encoder::encode`] and is
+    /// only a fallback. Implementors are expected to override it.
+    ///
+    /// Note: Literals are written right away. Use [`Encoder::encode`] when the message flow matters.
+    #[cfg(feature = "bytes")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+    fn encode_to(&self, message: &Self::Message<'_>, dst: &mut bytes::BytesMut) {
+        dst.extend_from_slice(&self.encode(message).dump());
+    }
 }
 
 /// An encoded message.
@@ -169,6 +213,11 @@ pub enum Fragment {
 pub(crate) struct EncodeContext {
     accumulator: Vec<u8>,
     items: Vec<Fragment>,
+    /// Number of written bytes when only counting (see [`EncodeContext::counting`]).
+    count: Option<usize>,
+    /// Buffer that receives all data when writing through (see [`EncodeContext::bytes`]).
+    #[cfg(feature = "bytes")]
+    bytes: Option<bytes::BytesMut>,
 }
 
 impl EncodeContext {
@@ -176,13 +225,59 @@ impl EncodeContext {
         Self::default()
     }
 
+    /// Create a context that only counts the written bytes and discards all data.
+    pub fn counting() -> Self {
+        Self {
+            count: Some(0),
+            ..Self::default()
+        }
+    }
+
+    /// Number of written bytes (only available when created by [`EncodeContext::counting`]).
+    pub fn count(&self) -> Option<usize> {
+        self.count
+    }
+
+    /// Create a context that appends all data to `dst` without creating fragments.
+    #[cfg(feature = "bytes")]
+    pub fn bytes(dst: bytes::BytesMut) -> Self {
+        Self {
+            bytes: Some(dst),
+            ..Self::default()
+        }
+    }
+
+    /// Take the buffer back (only available when created by [`EncodeContext::bytes`]).
+    #[cfg(feature = "bytes")]
+    pub fn into_bytes(self) -> Option<bytes::BytesMut> {
+        self.bytes
+    }
+
+    /// Returns `true` when no fragments are created, i.e., when counting or writing through.
+    fn is_flat(&self) -> bool {
+        #[cfg(feature = "bytes")]
+        if self.bytes.is_some() {
+            return true;
+        }
+
+        self.count.is_some()
+    }
+
     pub fn push_line(&mut self) {
+        if self.is_flat() {
+            return;
+        }
+
         self.items.push(Fragment::Line {
             data: std::mem::take(&mut self.accumulator),
         })
     }
 
     pub fn push_literal(&mut self, mode: LiteralMode) {
+        if self.is_flat() {
+            return;
+        }
+
         self.items.push(Fragment::Literal {
             data: std::mem::take(&mut self.accumulator),
             mode,
@@ -193,6 +288,7 @@ impl EncodeContext {
         let Self {
             accumulator,
             mut items,
+            ..
         } = self;
 
         if !accumulator.is_empty() {
@@ -220,7 +316,17 @@ impl EncodeContext {
 
 impl Write for EncodeContext {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        self.accumulator.extend_from_slice(buf);
+        #[cfg(feature = "bytes")]
+        if let Some(bytes) = self.bytes.as_mut() {
+            bytes.extend_from_slice(buf);
+            return Ok(buf.len());
+        }
+
+        match self.count.as_mut() {
+            Some(count) => *count += buf.len(),
+            None => self.accumulator.extend_from_slice(buf),
+        }
+
         Ok(buf.len())
     }
 
@@ -242,6 +348,26 @@ macro_rules! impl_encoder_for_codec {
                     items: encode_context.into_items(),
                 }
             }
+
+            fn encoded_len(&self, message: &Self::Message<'_>) -> usize {
+                let mut encode_context = EncodeContext::counting();
+                EncodeIntoContext::encode_ctx(message.borrow(), &mut encode_context).unwrap();
+
+                // `count` is always `Some` for a counting context.
+                encode_context.count().unwrap_or_default()
+            }
+
+            #[cfg(feature = "bytes")]
+            fn encode_to(&self, message: &Self::Message<'_>, dst: &mut bytes::BytesMut) {
+                dst.reserve(self.encoded_len(message));
+
+                // Moving the buffer into the context doesn't copy any data.
+                let mut encode_context = EncodeContext::bytes(std::mem::take(dst));
+                EncodeIntoContext::encode_ctx(message.borrow(), &mut encode_context).unwrap();
+
+                // The buffer is always `Some` for a writing-through context.
+                *dst = encode_context.into_bytes().unwrap_or_default();
+            }
         }
     };
 }
@@ -327,21 +453,54 @@ impl<'a> EncodeIntoContext for CommandBody<'a> {
                 ctx.write_all(b" ")?;
                 password.declassify().encode_ctx(ctx)
             }
-            CommandBody::Select { mailbox } => {
+            CommandBody::Select {
+                mailbox,
+                parameters,
+            } => {
                 ctx.write_all(b"SELECT")?;
                 ctx.write_all(b" ")?;
-                mailbox.encode_ctx(ctx)
+                mailbox.encode_ctx(ctx)?;
+
+                if !parameters.is_empty() {
+                    ctx.write_all(b" (")?;
+                    join_serializable(parameters, b" ", ctx)?;
+                    ctx.write_all(b")")?;
+                }
+
+                Ok(())
             }
             CommandBody::Unselect => ctx.write_all(b"UNSELECT"),
-            CommandBody::Examine { mailbox } => {
+            CommandBody::Examine {
+                mailbox,
+                parameters,
+            } => {
                 ctx.write_all(b"EXAMINE")?;
                 ctx.write_all(b" ")?;
-                mailbox.encode_ctx(ctx)
+                mailbox.encode_ctx(ctx)?;
+
+                if !parameters.is_empty() {
+                    ctx.write_all(b" (")?;
+                    join_serializable(parameters, b" ", ctx)?;
+                    ctx.write_all(b")")?;
+                }
+
+                Ok(())
             }
-            CommandBody::Create { mailbox } => {
+            CommandBody::Create {
+                mailbox,
+                parameters,
+            } => {
                 ctx.write_all(b"CREATE")?;
                 ctx.write_all(b" ")?;
-                mailbox.encode_ctx(ctx)
+                mailbox.encode_ctx(ctx)?;
+
+                if !parameters.is_empty() {
+                    ctx.write_all(b" (")?;
+                    join_serializable(parameters, b" ", ctx)?;
+                    ctx.write_all(b")")?;
+                }
+
+                Ok(())
             }
             CommandBody::Delete { mailbox } => {
                 ctx.write_all(b"DELETE")?;
@@ -659,6 +818,71 @@ impl<'a> EncodeIntoContext for CommandBody<'a> {
                     ctx.write_all(b")")
                 }
             }
+            #[cfg(feature = "ext_gmail")]
+            CommandBody::StoreGmailLabels {
+                sequence_set,
+                kind,
+                response,
+                labels,
+                uid,
+            } => {
+                if *uid {
+                    ctx.write_all(b"UID STORE ")?;
+                } else {
+                    ctx.write_all(b"STORE ")?;
+                }
+
+                sequence_set.encode_ctx(ctx)?;
+                ctx.write_all(b" ")?;
+
+                match kind {
+                    StoreType::Add => ctx.write_all(b"+")?,
+                    StoreType::Remove => ctx.write_all(b"-")?,
+                    StoreType::Replace => {}
+                }
+
+                ctx.write_all(b"X-GM-LABELS")?;
+
+                match response {
+                    StoreResponse::Answer => {}
+                    StoreResponse::Silent => ctx.write_all(b".SILENT")?,
+                }
+
+                ctx.write_all(b" ")?;
+                encode_label_list(labels, ctx)
+            }
+            #[cfg(feature = "ext_replace")]
+            CommandBody::Replace {
+                sequence,
+                mailbox,
+                flags,
+                date,
+                message,
+                uid,
+            } => {
+                if *uid {
+                    ctx.write_all(b"UID REPLACE ")?;
+                } else {
+                    ctx.write_all(b"REPLACE ")?;
+                }
+                sequence.encode_ctx(ctx)?;
+                ctx.write_all(b" ")?;
+                mailbox.encode_ctx(ctx)?;
+
+                if !flags.is_empty() {
+                    ctx.write_all(b" (")?;
+                    join_serializable(flags, b" ", ctx)?;
+                    ctx.write_all(b")")?;
+                }
+
+                if let Some(date) = date {
+                    ctx.write_all(b" ")?;
+                    date.encode_ctx(ctx)?;
+                }
+
+                ctx.write_all(b" ")?;
+                message.encode_ctx(ctx)
+            }
         }
     }
 }
@@ -937,6 +1161,8 @@ impl EncodeIntoContext for Sequence {
     fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
         match self {
             Sequence::Single(seq_no) => seq_no.encode_ctx(ctx),
+            // A degenerate range, e.g., `5:5` or `*:*`, is equivalent to a single number.
+            Sequence::Range(from, to) if from == to => from.encode_ctx(ctx),
             Sequence::Range(from, to) => {
                 from.encode_ctx(ctx)?;
                 ctx.write_all(b":")?;
@@ -1050,6 +1276,22 @@ impl<'a> EncodeIntoContext for MessageDataItemName<'a> {
                 join_serializable(section, b".", ctx)?;
                 ctx.write_all(b"]")
             }
+            #[cfg(feature = "ext_gmail")]
+            MessageDataItemName::XGmMsgId => ctx.write_all(b"X-GM-MSGID"),
+            #[cfg(feature = "ext_gmail")]
+            MessageDataItemName::XGmThrId => ctx.write_all(b"X-GM-THRID"),
+            #[cfg(feature = "ext_gmail")]
+            MessageDataItemName::XGmLabels => ctx.write_all(b"X-GM-LABELS"),
+            #[cfg(feature = "ext_preview")]
+            MessageDataItemName::Preview { lazy } => {
+                ctx.write_all(b"PREVIEW")?;
+
+                if *lazy {
+                    ctx.write_all(b" (LAZY)")?;
+                }
+
+                Ok(())
+            }
         }
     }
 }
@@ -1281,6 +1523,8 @@ impl<'a> EncodeIntoContext for Code<'a> {
             }
             #[cfg(feature = "ext_uidplus")]
             Code::UidNotSticky => ctx.write_all(b"UIDNOTSTICKY"),
+            #[cfg(feature = "ext_special_use")]
+            Code::UseAttr => ctx.write_all(b"USEATTR"),
             Code::Other(unknown) => unknown.encode_ctx(ctx),
         }
     }
@@ -1350,12 +1594,17 @@ impl<'a> EncodeIntoContext for Data<'a> {
                 join_serializable(items, b" ", ctx)?;
                 ctx.write_all(b")")?;
             }
-            Data::Search(seqs) => {
-                if seqs.is_empty() {
+            Data::Search { numbers, modseq } => {
+                if numbers.is_empty() {
                     ctx.write_all(b"* SEARCH")?;
                 } else {
                     ctx.write_all(b"* SEARCH ")?;
-                    join_serializable(seqs, b" ", ctx)?;
+                    join_serializable(numbers, b" ", ctx)?;
+                }
+
+                // `search-sort-mod-seq` requires at least one number.
+                if let (Some(modseq), false) = (modseq, numbers.is_empty()) {
+                    write!(ctx, " (MODSEQ {modseq})")?;
                 }
             }
             #[cfg(feature = "ext_sort_thread")]
@@ -1584,6 +1833,20 @@ impl<'a> EncodeIntoContext for MessageDataItem<'a> {
                 ctx.write_all(b"] ")?;
                 size.encode_ctx(ctx)
             }
+            #[cfg(feature = "ext_preview")]
+            Self::Preview(preview) => {
+                ctx.write_all(b"PREVIEW ")?;
+                preview.encode_ctx(ctx)
+            }
+            #[cfg(feature = "ext_gmail")]
+            Self::XGmMsgId(msgid) => write!(ctx, "X-GM-MSGID {msgid}"),
+            #[cfg(feature = "ext_gmail")]
+            Self::XGmThrId(thrid) => write!(ctx, "X-GM-THRID {thrid}"),
+            #[cfg(feature = "ext_gmail")]
+            Self::XGmLabels(labels) => {
+                ctx.write_all(b"X-GM-LABELS ")?;
+                encode_label_list(labels, ctx)
+            }
         }
     }
 }
@@ -2064,6 +2327,120 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_encoded_len() {
+        let commands = [
+            Command::new("A", CommandBody::Noop).unwrap(),
+            Command::new("A", CommandBody::login("alice", "pass").unwrap()).unwrap(),
+            Command::new(
+                "A",
+                CommandBody::login("alice", b"\xCA\xFE".as_ref()).unwrap(),
+            )
+            .unwrap(),
+            Command::new(
+                "A",
+                CommandBody::login(
+                    AString::from(Literal::unvalidated_non_sync(b"alice".as_ref())),
+                    "pass",
+                )
+                .unwrap(),
+            )
+            .unwrap(),
+            Command::new(
+                "A",
+                CommandBody::authenticate_with_ir(
+                    AuthMechanism::Plain,
+                    b"\x00alice\x00pass".as_ref(),
+                ),
+            )
+            .unwrap(),
+            Command::new(
+                "A",
+                CommandBody::fetch(
+                    "1:*,5",
+                    MacroOrMessageDataItemNames::Macro(Macro::Full),
+                    true,
+                )
+                .unwrap(),
+            )
+            .unwrap(),
+            Command::new(
+                "A",
+                CommandBody::append("INBOX", vec![Flag::Seen], None, b"Hello\r\n".as_ref())
+                    .unwrap(),
+            )
+            .unwrap(),
+        ];
+
+        for command in commands {
+            let expected = CommandCodec::default().encode(&command).dump().len();
+            assert_eq!(CommandCodec::default().encoded_len(&command), expected);
+        }
+
+        let response = Response::Data(Data::Fetch {
+            seq: NonZeroU32::new(12345).unwrap(),
+            items: Vec1::from(MessageDataItem::BodyExt {
+                section: None,
+                origin: None,
+                data: NString::from(Literal::unvalidated(b"ABCDE".as_ref())),
+            }),
+        });
+        let expected = ResponseCodec::default().encode(&response).dump().len();
+        assert_eq!(ResponseCodec::default().encoded_len(&response), expected);
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn test_encode_to() {
+        use bytes::BytesMut;
+
+        let commands = [
+            Command::new("A", CommandBody::Noop).unwrap(),
+            Command::new(
+                "A",
+                CommandBody::login("alice", b"\xCA\xFE".as_ref()).unwrap(),
+            )
+            .unwrap(),
+            Command::new(
+                "A",
+                CommandBody::append("INBOX", vec![Flag::Seen], None, b"Hello\r\n".as_ref())
+                    .unwrap(),
+            )
+            .unwrap(),
+        ];
+
+        let mut dst = BytesMut::new();
+        let mut expected = Vec::new();
+
+        for command in commands {
+            CommandCodec::default().encode_to(&command, &mut dst);
+            expected.extend(CommandCodec::default().encode(&command).dump());
+        }
+
+        assert_eq!(dst.as_ref(), expected.as_slice());
+
+        let response = Response::Data(Data::Fetch {
+            seq: NonZeroU32::new(12345).unwrap(),
+            items: Vec1::from(MessageDataItem::BodyExt {
+                section: None,
+                origin: None,
+                data: NString::from(Literal::unvalidated(b"ABCDE".as_ref())),
+            }),
+        });
+
+        // Existing data is kept.
+        let mut dst = BytesMut::from(b"* OK ...\r\n".as_ref());
+        ResponseCodec::default().encode_to(&response, &mut dst);
+        assert_eq!(
+            dst.as_ref(),
+            [
+                b"* OK ...\r\n".as_ref(),
+                &ResponseCodec::default().encode(&response).dump()
+            ]
+            .concat()
+        );
+    }
+
     #[test]
     fn test_encode_response() {
         kat_encoder::<ResponseCodec, Response<'_>, &[Fragment]>(&[
@@ -0,0 +1,158 @@
+//! # Parsers for core grammar elements.
+//!
+//! Crates implementing IMAP extensions can reuse these [`nom`] parsers instead of reimplementing
+//! the (surprisingly subtle) core grammar. All parsers are streaming parsers, i.e., they return
+//! [`nom::Err::Incomplete`] when more data is required. This includes a literal whose data is
+//! not available yet.
+//!
+//! Note: Errors are reported as [`nom::error::Error`] of nom 7, which is re-exported as
+//! [`imap_codec::nom`](crate::nom). A new major version of nom is thus a breaking change.
+//!
+//! Note: This module is named `parse` and not `core`, because the crate root already uses `core`
+//! internally. It contains the parsers requested as `codec::core`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use imap_codec::{
+//!     nom::{
+//!         bytes::streaming::tag,
+//!         sequence::{separated_pair, tuple},
+//!     },
+//!     parse::{atom, nz_number, ParseResult},
+//! };
+//!
+//! /// `x-limit = "X-LIMIT" SP atom SP nz-number`
+//! fn x_limit(input: &[u8]) -> ParseResult<(String, u32)> {
+//!     let (remaining, (_, (name, limit))) =
+//!         tuple((tag(b"X-LIMIT "), separated_pair(atom, tag(b" "), nz_number)))(input)?;
+//!
+//!     Ok((remaining, (name.as_ref().to_owned(), limit.get())))
+//! }
+//!
+//! assert_eq!(
+//!     x_limit(b"X-LIMIT messages 100\r\n").unwrap(),
+//!     (b"\r\n".as_ref(), ("messages".to_owned(), 100))
+//! );
+//! assert!(x_limit(b"X-LIMIT messages 0\r\n").is_err());
+//! ```
+
+use std::num::NonZeroU32;
+
+use imap_types::core::{AString, Atom, Literal, Quoted};
+use nom::{
+    error::{Error, ErrorKind},
+    Needed,
+};
+
+use crate::{
+    core,
+    decode::{IMAPErrorKind, IMAPParseError, IMAPResult},
+};
+
+/// Result of the parsers in this module.
+pub type ParseResult<'a, O> = Result<(&'a [u8], O), nom::Err<Error<&'a [u8]>>>;
+
+fn convert<'a, O>(result: IMAPResult<'a, &'a [u8], O>) -> ParseResult<'a, O> {
+    fn error<'a>(IMAPParseError { input, kind }: IMAPParseError<'a, &'a [u8]>) -> Error<&'a [u8]> {
+        let kind = match kind {
+            IMAPErrorKind::Nom(kind) => kind,
+            _ => ErrorKind::Verify,
+        };
+
+        Error::new(input, kind)
+    }
+
+    result.map_err(|err| match err {
+        nom::Err::Incomplete(needed) => nom::Err::Incomplete(needed),
+        nom::Err::Failure(IMAPParseError {
+            kind: IMAPErrorKind::Literal { length, .. },
+            ..
+        }) => nom::Err::Incomplete(Needed::new(length as usize)),
+        nom::Err::Error(err) => nom::Err::Error(error(err)),
+        nom::Err::Failure(err) => nom::Err::Failure(error(err)),
+    })
+}
+
+/// `number = 1*DIGIT`
+///
+/// Note: Leading zeros are allowed, i.e., `007` is parsed as `7`.
+pub fn number(input: &[u8]) -> ParseResult<u32> {
+    convert(core::number(input))
+}
+
+/// `number64 = 1*DIGIT` (RFC 9051)
+pub fn number64(input: &[u8]) -> ParseResult<u64> {
+    convert(core::number64(input))
+}
+
+/// `nz-number = digit-nz *DIGIT`
+///
+/// Note: Leading zeros are rejected, i.e., `0`, `00`, and `007` are rejected.
+pub fn nz_number(input: &[u8]) -> ParseResult<NonZeroU32> {
+    convert(core::nz_number(input))
+}
+
+/// `atom = 1*ATOM-CHAR`
+pub fn atom(input: &[u8]) -> ParseResult<Atom> {
+    convert(core::atom(input))
+}
+
+/// `astring = 1*ASTRING-CHAR / string`
+pub fn astring(input: &[u8]) -> ParseResult<AString> {
+    convert(core::astring(input))
+}
+
+/// `quoted = DQUOTE *QUOTED-CHAR DQUOTE`
+pub fn quoted(input: &[u8]) -> ParseResult<Quoted> {
+    convert(core::quoted(input))
+}
+
+/// `literal = "{" number ["+"] "}" CRLF *CHAR8`
+pub fn literal(input: &[u8]) -> ParseResult<Literal> {
+    convert(core::literal(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_core() {
+        assert_eq!(number(b"007 ").unwrap(), (b" ".as_ref(), 7));
+        assert_eq!(number64(b"4294967296 ").unwrap(), (b" ".as_ref(), 1 << 32));
+        assert_eq!(
+            nz_number(b"42 ").unwrap(),
+            (b" ".as_ref(), NonZeroU32::new(42).unwrap())
+        );
+        assert!(matches!(nz_number(b"0 "), Err(nom::Err::Error(_))));
+        assert!(matches!(number(b"42"), Err(nom::Err::Incomplete(_))));
+
+        assert_eq!(atom(b"abc ").unwrap().1, Atom::try_from("abc").unwrap());
+        assert_eq!(
+            astring(b"\"a b\" ").unwrap().1,
+            AString::try_from("a b").unwrap()
+        );
+        assert_eq!(
+            quoted(b"\"a\\\"b\" ").unwrap().1,
+            Quoted::try_from("a\"b").unwrap()
+        );
+        assert_eq!(
+            literal(b"{3}\r\nabc ").unwrap().1,
+            Literal::try_from("abc").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_literal_incomplete() {
+        assert_eq!(
+            literal(b"{3}\r\n"),
+            Err(nom::Err::Incomplete(Needed::new(3)))
+        );
+        assert!(matches!(literal(b"{3}\r\na"), Err(nom::Err::Incomplete(_))));
+        assert!(matches!(
+            literal(b"{3}\r\na\x00c"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+}
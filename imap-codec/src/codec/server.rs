@@ -0,0 +1,124 @@
+//! # Server-side codec functions.
+//!
+//! A server decodes commands (including authenticate data and the `DONE` of `IDLE`), and encodes
+//! greetings and responses. This module only exposes functions for this direction so that the
+//! client-side functions can't be used by accident. See [`client`](crate::client) for the
+//! other direction.
+//!
+//! All functions are thin wrappers around the codecs using their default configuration.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use imap_codec::{
+//!     imap_types::{
+//!         command::CommandBody,
+//!         response::{Greeting, Response, Status},
+//!     },
+//!     server,
+//! };
+//!
+//! let greeting = Greeting::ok(None, "ready").unwrap();
+//! assert_eq!(server::encode_greeting(&greeting).dump(), b"* OK ready\r\n");
+//!
+//! let (remaining, command) = server::decode_command(b"A1 NOOP\r\n").unwrap();
+//! assert_eq!(command.body, CommandBody::Noop);
+//! assert!(remaining.is_empty());
+//!
+//! let response = Response::Status(Status::ok(Some(command.tag), None, "done").unwrap());
+//! assert_eq!(server::encode_response(&response).dump(), b"A1 OK done\r\n");
+//! ```
+
+use imap_types::{
+    auth::AuthenticateData,
+    command::Command,
+    extensions::idle::IdleDone,
+    response::{Greeting, Response},
+};
+
+use crate::{
+    decode::{AuthenticateDataDecodeError, CommandDecodeError, Decoder, IdleDoneDecodeError},
+    encode::{Encoded, Encoder},
+    AuthenticateDataCodec, CommandCodec, GreetingCodec, IdleDoneCodec, ResponseCodec,
+};
+
+/// Decode a command.
+pub fn decode_command(input: &[u8]) -> Result<(&[u8], Command), CommandDecodeError> {
+    CommandCodec::default().decode(input)
+}
+
+/// Decode authenticate data.
+pub fn decode_authenticate_data(
+    input: &[u8],
+) -> Result<(&[u8], AuthenticateData), AuthenticateDataDecodeError> {
+    AuthenticateDataCodec::default().decode(input)
+}
+
+/// Decode the `DONE` ending an `IDLE`.
+pub fn decode_idle_done(input: &[u8]) -> Result<(&[u8], IdleDone), IdleDoneDecodeError> {
+    IdleDoneCodec::default().decode(input)
+}
+
+/// Encode a greeting.
+pub fn encode_greeting(greeting: &Greeting) -> Encoded {
+    GreetingCodec::default().encode(greeting)
+}
+
+/// Encode a response.
+pub fn encode_response(response: &Response) -> Encoded {
+    ResponseCodec::default().encode(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use imap_types::core::LiteralMode;
+
+    use super::*;
+
+    #[test]
+    fn test_decode_command_dispatch() {
+        let tests = [
+            (b"a CAPABILITY\r\n".as_ref(), "a", "CAPABILITY"),
+            (b"a1 noop\r\n", "a1", "NOOP"),
+            (b"A.2 LOGOUT\r\n", "A.2", "LOGOUT"),
+            (b"3 LOGIN alice \"pass word\"\r\n", "3", "LOGIN"),
+            (b"x] SELECT INBOX\r\n", "x]", "SELECT"),
+            (b"a EXAMINE \"Sent Items\"\r\n", "a", "EXAMINE"),
+            (b"a LIST \"\" *\r\n", "a", "LIST"),
+            (b"a STATUS INBOX (MESSAGES UNSEEN)\r\n", "a", "STATUS"),
+            (b"a APPEND INBOX {5+}\r\nHello\r\n", "a", "APPEND"),
+            (b"a FETCH 1:* (FLAGS BODY.PEEK[])\r\n", "a", "FETCH"),
+            (b"a UID STORE 1 +FLAGS (\\Seen)\r\n", "a", "STORE"),
+            (b"a UID SEARCH UNSEEN\r\n", "a", "SEARCH"),
+            (b"a IDLE\r\n", "a", "IDLE"),
+        ];
+
+        for (input, tag, name) in tests {
+            let (remaining, command) = decode_command(input).unwrap();
+            assert!(remaining.is_empty());
+            assert_eq!(command.tag.as_ref(), tag);
+            assert_eq!(command.name(), name);
+        }
+
+        assert_eq!(
+            decode_command(b"a LOGIN alice {5}\r\n"),
+            Err(CommandDecodeError::LiteralFound {
+                tag: imap_types::core::Tag::try_from("a").unwrap(),
+                length: 5,
+                mode: LiteralMode::Sync,
+            })
+        );
+        assert_eq!(
+            decode_command(b"a NOOP"),
+            Err(CommandDecodeError::Incomplete)
+        );
+        assert_eq!(
+            decode_command(b"a XYZ\r\n"),
+            Err(CommandDecodeError::Failed)
+        );
+        assert_eq!(
+            decode_command(b"+ NOOP\r\n"),
+            Err(CommandDecodeError::Failed)
+        );
+    }
+}
@@ -0,0 +1,644 @@
+//! # Streaming of (large) literals.
+//!
+//! [`Decoder`](crate::decode::Decoder)s require a complete message, i.e., all literals must be buffered before a message can be decoded.
+//! This is impractical for servers or proxies handling multi-megabyte message bodies.
+//!
+//! The [`LiteralStreamer`] doesn't decode messages but splits a stream of bytes into lines and literal data.
+//! A message containing a literal, e.g., `* 1 FETCH (BODY[] {5}\r\nHello)\r\n`, yields ...
+//!
+//! 1. a [`StreamEvent::Line`] (`* 1 FETCH (BODY[] {5}\r\n`) announcing the literal,
+//! 2. one or more [`StreamEvent::LiteralData`] chunks (`Hello`) as soon as the data is available,
+//! 3. a [`StreamEvent::LiteralEnd`], and
+//! 4. a [`StreamEvent::Line`] (`)\r\n`) with the rest of the message.
+//!
+//! Only (incomplete) lines need to be buffered by the caller; literal data is never buffered.
+//!
+//! Literals are recognized by the same parsers used by the [`Decoder`](crate::decode::Decoder)s.
+//! Thus, a line ending in, e.g., `A1 NO No such mailbox foo{5}\r\n` doesn't announce a literal because `foo{5}` is part of a `text`.
+//! For this, the streamer keeps the lines of a message announcing literals, but replaces all literal data with a placeholder.
+//! The message is parsed again for every line ending in something that looks like a literal announcement.
+//! Thus, the buffered lines are limited by [`LiteralStreamer::with_max_message_length`], and [`LiteralStreamer::feed`] fails when a message exceeds the limit.
+//! Messages that can't be parsed end with the line that made the parser fail.
+//!
+//! When the connection is closed, [`LiteralStreamer::finish`] tells whether this happened at a message boundary
+//! or whether the last message was truncated.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use imap_codec::stream::{LiteralStreamer, StreamEvent};
+//!
+//! let mut streamer = LiteralStreamer::responses();
+//! let mut body = Vec::new();
+//!
+//! let consumed = streamer
+//!     .feed(b"* 1 FETCH (BODY[] {5}\r\nHel", |event| {
+//!         if let StreamEvent::LiteralData(data) = event {
+//!             body.extend_from_slice(data);
+//!         }
+//!     })
+//!     .unwrap();
+//! assert_eq!(consumed, 26);
+//!
+//! let consumed = streamer
+//!     .feed(b"lo)\r", |event| {
+//!         if let StreamEvent::LiteralData(data) = event {
+//!             body.extend_from_slice(data);
+//!         }
+//!     })
+//!     .unwrap();
+//! // `)\r` is an incomplete line and must be fed again together with more data.
+//! assert_eq!(consumed, 2);
+//! assert_eq!(body, b"Hello");
+//! ```
+
+use std::ops::Range;
+
+use imap_types::core::LiteralMode;
+
+use crate::{
+    command::command,
+    decode::{IMAPErrorKind, IMAPParseError, IMAPResult},
+    response::response,
+};
+
+/// Default for the maximum length of a message, see [`LiteralStreamer::with_max_message_length`].
+pub const DEFAULT_MAX_MESSAGE_LENGTH: usize = 64 * 1024;
+
+/// Splits a stream of bytes into lines and literal data.
+///
+/// See the [module documentation](self) for details.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LiteralStreamer {
+    grammar: Grammar,
+    max_message_length: usize,
+    remaining_literal: Option<u32>,
+    /// Lines of the current message with all literal data replaced by a placeholder.
+    ///
+    /// Empty at a message boundary.
+    message: Vec<u8>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Grammar {
+    Command,
+    Response,
+}
+
+/// Event produced by [`LiteralStreamer::feed`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StreamEvent<'a> {
+    /// A complete line including CRLF.
+    Line {
+        data: &'a [u8],
+        /// Literal announced at the end of this line.
+        literal: Option<LiteralAnnouncement>,
+    },
+    /// A chunk of literal data.
+    LiteralData(&'a [u8]),
+    /// All announced literal data was streamed.
+    LiteralEnd,
+}
+
+/// Error produced by [`LiteralStreamer::finish`] when the input ended in the middle of a message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TruncatedError {
+    /// The input ended in the middle of a literal.
+    Literal {
+        /// Number of missing literal bytes.
+        remaining: u32,
+    },
+    /// The input ended in the middle of a line, or after a literal but before the line finishing the message.
+    Message,
+}
+
+/// Error produced by [`LiteralStreamer::feed`] when a message exceeds the maximum length.
+///
+/// The streamer can't tell where the next message starts, so the connection should be closed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MessageTooLongError {
+    /// Number of bytes consumed before the line that exceeded the limit.
+    pub consumed: usize,
+}
+
+/// Literal announced at the end of a line, e.g., `{42}\r\n` or `{42+}\r\n`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LiteralAnnouncement {
+    pub length: u32,
+    pub mode: LiteralMode,
+}
+
+impl LiteralStreamer {
+    /// Create a streamer for commands, i.e., for the client-to-server direction.
+    pub fn commands() -> Self {
+        Self::new(Grammar::Command)
+    }
+
+    /// Create a streamer for responses (including the greeting), i.e., for the server-to-client direction.
+    pub fn responses() -> Self {
+        Self::new(Grammar::Response)
+    }
+
+    fn new(grammar: Grammar) -> Self {
+        Self {
+            grammar,
+            max_message_length: DEFAULT_MAX_MESSAGE_LENGTH,
+            remaining_literal: None,
+            message: Vec::new(),
+        }
+    }
+
+    /// Set the maximum length of a message announcing literals (excluding literal data).
+    ///
+    /// Such a message is buffered and parsed again for every literal, which is quadratic in the
+    /// number of literals. The limit bounds memory and time spent on a single message. Messages
+    /// without literals are not buffered and not limited. Defaults to [`DEFAULT_MAX_MESSAGE_LENGTH`].
+    pub fn with_max_message_length(mut self, max_message_length: usize) -> Self {
+        self.max_message_length = max_message_length;
+        self
+    }
+
+    /// Get the maximum length of a message announcing literals (excluding literal data).
+    pub fn max_message_length(&self) -> usize {
+        self.max_message_length
+    }
+
+    /// Returns `true` when the streamer is in the middle of a literal.
+    pub fn in_literal(&self) -> bool {
+        self.remaining_literal.is_some()
+    }
+
+    /// Signal the end of input, e.g., because the connection was closed.
+    ///
+    /// `unconsumed` are the bytes that were not consumed by [`LiteralStreamer::feed`].
+    /// Returns `Ok(())` when the input ended cleanly at a message boundary.
+    pub fn finish(&self, unconsumed: &[u8]) -> Result<(), TruncatedError> {
+        if let Some(remaining) = self.remaining_literal {
+            return Err(TruncatedError::Literal { remaining });
+        }
+
+        if !self.message.is_empty() || !unconsumed.is_empty() {
+            return Err(TruncatedError::Message);
+        }
+
+        Ok(())
+    }
+
+    /// Feed `input` into the streamer and pass all resulting events to `sink`.
+    ///
+    /// Returns the number of consumed bytes. Unconsumed bytes form an incomplete line and must be
+    /// fed again (prepended to more data).
+    ///
+    /// Fails when a message announcing literals exceeds the
+    /// [maximum message length](LiteralStreamer::with_max_message_length).
+    pub fn feed<'a, F>(
+        &mut self,
+        input: &'a [u8],
+        mut sink: F,
+    ) -> Result<usize, MessageTooLongError>
+    where
+        F: FnMut(StreamEvent<'a>),
+    {
+        let mut consumed = 0;
+
+        loop {
+            let input = &input[consumed..];
+
+            match self.remaining_literal {
+                Some(0) => {
+                    self.remaining_literal = None;
+                    sink(StreamEvent::LiteralEnd);
+                }
+                Some(remaining) => {
+                    if input.is_empty() {
+                        break;
+                    }
+
+                    let length = input.len().min(remaining as usize);
+                    consumed += length;
+                    // `length` is at most `remaining`, i.e., fits into a `u32`.
+                    self.remaining_literal = Some(remaining - length as u32);
+                    sink(StreamEvent::LiteralData(&input[..length]));
+                }
+                None => {
+                    let position = match input.iter().position(|byte| *byte == b'\n') {
+                        Some(position) => position,
+                        None => break,
+                    };
+
+                    let line = &input[..=position];
+                    let literal = self.push_line(line, consumed)?;
+
+                    consumed += line.len();
+                    self.remaining_literal = literal.map(|literal| literal.length);
+                    sink(StreamEvent::Line {
+                        data: line,
+                        literal,
+                    });
+                }
+            }
+        }
+
+        Ok(consumed)
+    }
+
+    /// Append a complete `line` to the current message and return the literal it announces (if any).
+    fn push_line(
+        &mut self,
+        line: &[u8],
+        consumed: usize,
+    ) -> Result<Option<LiteralAnnouncement>, MessageTooLongError> {
+        // A line not ending in something that looks like a literal announcement ends the message.
+        let digits = match parse_literal_announcement(line) {
+            Some((digits, _)) => self.message.len() + digits.start..self.message.len() + digits.end,
+            None => {
+                self.message.clear();
+                return Ok(None);
+            }
+        };
+
+        if self.message.len() + line.len() > self.max_message_length {
+            return Err(MessageTooLongError { consumed });
+        }
+
+        self.message.extend_from_slice(line);
+
+        let literal = match self.grammar {
+            Grammar::Command => announced_literal(command(&self.message)),
+            Grammar::Response => announced_literal(response(&self.message)),
+        };
+
+        match literal {
+            Some(LiteralAnnouncement { length, .. }) => {
+                // Replace the literal with a placeholder of at most one byte, so that literal data
+                // is never buffered. Keeping empty literals empty doesn't change the parsing result.
+                self.message
+                    .splice(digits, [if length == 0 { b'0' } else { b'1' }]);
+                if length != 0 {
+                    self.message.push(b'a');
+                }
+            }
+            None => self.message.clear(),
+        }
+
+        Ok(literal)
+    }
+}
+
+/// Parse `"{" number ["+"] "}" CRLF` at the end of a line.
+///
+/// Returns the position of `number` in `line` and the announcement. Whether this really announces
+/// a literal depends on the grammar, e.g., it doesn't when it's part of a `text`.
+pub(crate) fn parse_literal_announcement(
+    line: &[u8],
+) -> Option<(Range<usize>, LiteralAnnouncement)> {
+    let rest = line.strip_suffix(b"\n")?;
+    #[cfg(not(feature = "quirk_crlf_relaxed"))]
+    let rest = rest.strip_suffix(b"\r")?;
+    #[cfg(feature = "quirk_crlf_relaxed")]
+    let rest = rest.strip_suffix(b"\r").unwrap_or(rest);
+    let rest = rest.strip_suffix(b"}")?;
+
+    let (rest, mode) = match rest.strip_suffix(b"+") {
+        Some(rest) => (rest, LiteralMode::NonSync),
+        None => (rest, LiteralMode::Sync),
+    };
+
+    let start = rest.iter().rposition(|byte| *byte == b'{')? + 1;
+    let digits = &rest[start..];
+
+    if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+
+    // # Safety
+    //
+    // `unwrap` is safe because `digits` contains ASCII-only characters.
+    let length = std::str::from_utf8(digits).unwrap().parse().ok()?;
+
+    Some((start..rest.len(), LiteralAnnouncement { length, mode }))
+}
+
+/// Returns the literal the parser stopped at, i.e., the literal announced at the end of the input.
+///
+/// Any other result, including a parsing error, ends the message.
+fn announced_literal<O>(result: IMAPResult<&[u8], O>) -> Option<LiteralAnnouncement> {
+    match result {
+        Err(nom::Err::Error(IMAPParseError {
+            kind: IMAPErrorKind::Literal { length, mode, .. },
+            ..
+        }))
+        | Err(nom::Err::Failure(IMAPParseError {
+            kind: IMAPErrorKind::Literal { length, mode, .. },
+            ..
+        })) => Some(LiteralAnnouncement { length, mode }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode::Decoder, ResponseCodec};
+
+    #[test]
+    fn test_literal_announcement() {
+        let tests = [
+            (
+                LiteralStreamer::commands(),
+                b"A LOGIN {5}\r\n".as_ref(),
+                Some((5, LiteralMode::Sync)),
+            ),
+            (
+                LiteralStreamer::commands(),
+                b"A LOGIN {0+}\r\n",
+                Some((0, LiteralMode::NonSync)),
+            ),
+            (LiteralStreamer::commands(), b"A NOOP\r\n", None),
+            (LiteralStreamer::commands(), b"A NOOP {5}\r\n", None),
+            (
+                LiteralStreamer::responses(),
+                b"* 1 FETCH (BODY[] {42}\r\n",
+                Some((42, LiteralMode::Sync)),
+            ),
+            (
+                LiteralStreamer::responses(),
+                b"* 1 FETCH (BODY[] {}\r\n",
+                None,
+            ),
+            (
+                LiteralStreamer::responses(),
+                b"* 1 FETCH (BODY[] {4294967296}\r\n",
+                None,
+            ),
+            // `text` can't contain a literal.
+            (
+                LiteralStreamer::responses(),
+                b"A1 NO No such mailbox foo{5}\r\n",
+                None,
+            ),
+            (LiteralStreamer::responses(), b"* OK Hello {5}\r\n", None),
+            (LiteralStreamer::responses(), b"+ {5}\r\n", None),
+            // A command isn't a response and vice versa.
+            (LiteralStreamer::responses(), b"A LOGIN {5}\r\n", None),
+            (
+                LiteralStreamer::commands(),
+                b"* 1 FETCH (BODY[] {5}\r\n",
+                None,
+            ),
+        ];
+
+        for (mut streamer, test, expected) in tests {
+            let mut got = None;
+            streamer
+                .feed(test, |event| {
+                    if let StreamEvent::Line { literal, .. } = event {
+                        got = literal.map(|LiteralAnnouncement { length, mode }| (length, mode));
+                    }
+                })
+                .unwrap();
+            assert_eq!(expected, got);
+            assert_eq!(expected.is_some(), streamer.finish(b"").is_err());
+        }
+    }
+
+    #[cfg(feature = "ext_binary")]
+    #[test]
+    fn test_stream_literal8() {
+        let mut streamer = LiteralStreamer::responses();
+        let mut literals = Vec::new();
+
+        let input = b"* 1 FETCH (BINARY[1] ~{3}\r\n\x00\x01\x02 BINARY[2] ~{0}\r\n)\r\n";
+        let consumed = streamer
+            .feed(input, |event| {
+                if let StreamEvent::Line {
+                    literal: Some(literal),
+                    ..
+                } = event
+                {
+                    literals.push(literal.length);
+                }
+            })
+            .unwrap();
+
+        assert_eq!(consumed, input.len());
+        assert_eq!(streamer.finish(b""), Ok(()));
+        assert_eq!(literals, [3, 0]);
+    }
+
+    #[test]
+    fn test_stream_max_message_length() {
+        let mut streamer = LiteralStreamer::commands().with_max_message_length(16);
+        assert_eq!(streamer.max_message_length(), 16);
+
+        // Messages without literals are not buffered ...
+        let input = b"A LOGIN aaaaaaaaaaaaaaaa bbbbbbbbbbbbbbbb\r\n";
+        assert_eq!(streamer.feed(input, |_| {}), Ok(input.len()));
+
+        // ... but messages announcing literals are.
+        let input = b"A LOGIN {5}\r\nalice {3}\r\nbob\r\n";
+        assert_eq!(
+            streamer.feed(input, |_| {}),
+            Err(MessageTooLongError { consumed: 18 })
+        );
+    }
+
+    #[test]
+    fn test_stream_agrees_with_decoder() {
+        let input =
+            b"A1 NO No such mailbox foo{5}\r\n* 1 FETCH (BODY[] {5}\r\nA1 OK)\r\n* 2 EXISTS\r\n";
+
+        let mut messages = Vec::new();
+        let mut remaining = input.as_ref();
+        while !remaining.is_empty() {
+            let (rem, _) = ResponseCodec::default().decode(remaining).unwrap();
+            messages.push(&remaining[..remaining.len() - rem.len()]);
+            remaining = rem;
+        }
+
+        let mut streamer = LiteralStreamer::responses();
+        let mut streamed = vec![Vec::new()];
+        let consumed = streamer
+            .feed(input, |event| match event {
+                StreamEvent::Line { data, literal } => {
+                    streamed.last_mut().unwrap().extend_from_slice(data);
+                    if literal.is_none() {
+                        streamed.push(Vec::new());
+                    }
+                }
+                StreamEvent::LiteralData(data) => {
+                    streamed.last_mut().unwrap().extend_from_slice(data)
+                }
+                StreamEvent::LiteralEnd => {}
+            })
+            .unwrap();
+        streamed.pop();
+
+        assert_eq!(consumed, input.len());
+        assert_eq!(streamer.finish(b""), Ok(()));
+        assert_eq!(messages, streamed);
+    }
+
+    #[test]
+    fn test_stream_command_literals() {
+        let mut streamer = LiteralStreamer::commands();
+        let mut events = Vec::new();
+
+        let input = b"A LOGIN {5+}\r\nalice {3}\r\nbob\r\nB NOOP {5}\r\n";
+        let consumed = streamer.feed(input, |event| events.push(event)).unwrap();
+
+        assert_eq!(consumed, input.len());
+        assert_eq!(streamer.finish(b""), Ok(()));
+        assert_eq!(
+            events,
+            [
+                StreamEvent::Line {
+                    data: b"A LOGIN {5+}\r\n",
+                    literal: Some(LiteralAnnouncement {
+                        length: 5,
+                        mode: LiteralMode::NonSync
+                    }),
+                },
+                StreamEvent::LiteralData(b"alice"),
+                StreamEvent::LiteralEnd,
+                StreamEvent::Line {
+                    data: b" {3}\r\n",
+                    literal: Some(LiteralAnnouncement {
+                        length: 3,
+                        mode: LiteralMode::Sync
+                    }),
+                },
+                StreamEvent::LiteralData(b"bob"),
+                StreamEvent::LiteralEnd,
+                StreamEvent::Line {
+                    data: b"\r\n",
+                    literal: None,
+                },
+                // Invalid, thus, not a literal.
+                StreamEvent::Line {
+                    data: b"B NOOP {5}\r\n",
+                    literal: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stream_events() {
+        let mut streamer = LiteralStreamer::responses();
+        let mut events = Vec::new();
+
+        let input = b"* 1 FETCH (BODY[] {5}\r\nHello BODY[TEXT] {0}\r\n)\r\n* 2 EXISTS\r\n* 3";
+        let consumed = streamer.feed(input, |event| events.push(event)).unwrap();
+
+        assert_eq!(consumed, input.len() - 3);
+        assert!(!streamer.in_literal());
+        assert_eq!(
+            events,
+            [
+                StreamEvent::Line {
+                    data: b"* 1 FETCH (BODY[] {5}\r\n",
+                    literal: Some(LiteralAnnouncement {
+                        length: 5,
+                        mode: LiteralMode::Sync
+                    }),
+                },
+                StreamEvent::LiteralData(b"Hello"),
+                StreamEvent::LiteralEnd,
+                StreamEvent::Line {
+                    data: b" BODY[TEXT] {0}\r\n",
+                    literal: Some(LiteralAnnouncement {
+                        length: 0,
+                        mode: LiteralMode::Sync
+                    }),
+                },
+                StreamEvent::LiteralEnd,
+                StreamEvent::Line {
+                    data: b")\r\n",
+                    literal: None,
+                },
+                StreamEvent::Line {
+                    data: b"* 2 EXISTS\r\n",
+                    literal: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_finish() {
+        let tests: [(&[u8], Result<(), TruncatedError>); 6] = [
+            (b"", Ok(())),
+            (b"* 1 EXISTS\r\n", Ok(())),
+            (b"* 1 FETCH (BODY[] {5}\r\nHello)\r\n", Ok(())),
+            (b"* 1 EXI", Err(TruncatedError::Message)),
+            (
+                b"* 1 FETCH (BODY[] {5}\r\nHel",
+                Err(TruncatedError::Literal { remaining: 2 }),
+            ),
+            (
+                b"* 1 FETCH (BODY[] {5}\r\nHello",
+                Err(TruncatedError::Message),
+            ),
+        ];
+
+        for (input, expected) in tests {
+            let mut streamer = LiteralStreamer::responses();
+            let consumed = streamer.feed(input, |_| {}).unwrap();
+
+            assert_eq!(streamer.finish(&input[consumed..]), expected);
+        }
+    }
+
+    #[test]
+    fn test_stream_large_literal_in_chunks() {
+        const LENGTH: usize = 1024 * 1024;
+        const CHUNK_SIZE: usize = 1000;
+
+        let mut input = format!("* 1 FETCH (BODY[] {{{LENGTH}}}\r\n").into_bytes();
+        input.extend((0..LENGTH).map(|i| (i % 251) as u8));
+        input.extend_from_slice(b")\r\n");
+
+        let mut streamer = LiteralStreamer::responses();
+        let mut buffer = Vec::new();
+        let mut lines = Vec::new();
+        let mut literal_length = 0;
+        let mut literal_ok = true;
+        let mut literal_ends = 0;
+
+        for chunk in input.chunks(CHUNK_SIZE) {
+            buffer.extend_from_slice(chunk);
+
+            let consumed = streamer
+                .feed(&buffer, |event| match event {
+                    StreamEvent::Line { data, .. } => lines.push(data.to_vec()),
+                    StreamEvent::LiteralData(data) => {
+                        assert!(data.len() <= CHUNK_SIZE);
+                        literal_ok &= data
+                            .iter()
+                            .enumerate()
+                            .all(|(i, byte)| *byte == ((literal_length + i) % 251) as u8);
+                        literal_length += data.len();
+                    }
+                    StreamEvent::LiteralEnd => literal_ends += 1,
+                })
+                .unwrap();
+
+            buffer.drain(..consumed);
+            // Literal data is never buffered.
+            assert!(!streamer.in_literal() || buffer.is_empty());
+        }
+
+        assert!(buffer.is_empty());
+        assert_eq!(
+            lines,
+            [
+                format!("* 1 FETCH (BODY[] {{{LENGTH}}}\r\n").into_bytes(),
+                b")\r\n".to_vec()
+            ]
+        );
+        assert_eq!(literal_length, LENGTH);
+        assert!(literal_ok);
+        assert_eq!(literal_ends, 1);
+    }
+}
@@ -0,0 +1,245 @@
+//! # Literal-driven transmission of commands.
+//!
+//! A command containing synchronizing literals can't be sent in one go.
+//! The client must send everything up to (and including) the literal announcement, e.g., `A LOGIN {5}\r\n`,
+//! and wait for the server's command continuation request (`+ ...`) before it may send the literal data.
+//!
+//! The [`CommandTransmitter`] keeps track of this.
+//! [`CommandTransmitter::next_bytes`] yields all bytes that can be sent without waiting.
+//! Once it returns `None` and [`CommandTransmitter::awaiting_continuation`] is `true`, the client must read
+//! the server's response and pass a received [`CommandContinuationRequest`] to
+//! [`CommandTransmitter::continuation`].
+//! Non-synchronizing literals (`{5+}`) are sent right away.
+//!
+//! Note: The server may also reject a literal by sending a tagged `NO` or `BAD` response instead.
+//! In this case, the command was aborted and the transmitter should be dropped.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use imap_codec::{
+//!     decode::Decoder,
+//!     imap_types::{command::Command, response::Response},
+//!     transmit::CommandTransmitter,
+//!     CommandCodec, ResponseCodec,
+//! };
+//!
+//! let (_, command) = CommandCodec::default()
+//!     .decode(b"A LOGIN {5}\r\nalice secret\r\n")
+//!     .unwrap();
+//!
+//! let mut transmitter = CommandTransmitter::new(&command);
+//!
+//! assert_eq!(transmitter.next_bytes().unwrap(), b"A LOGIN {5}\r\n");
+//! assert!(transmitter.awaiting_continuation());
+//! assert_eq!(transmitter.next_bytes(), None);
+//!
+//! let (_, response) = ResponseCodec::default().decode(b"+ Ready\r\n").unwrap();
+//! if let Response::CommandContinuationRequest(continuation) = response {
+//!     transmitter.continuation(&continuation).unwrap();
+//! }
+//!
+//! assert_eq!(transmitter.next_bytes().unwrap(), b"alice secret\r\n");
+//! assert!(transmitter.is_done());
+//! ```
+
+use imap_types::{command::Command, core::LiteralMode, response::CommandContinuationRequest};
+
+use crate::{
+    encode::{Encoded, Encoder, Fragment},
+    CommandCodec,
+};
+
+/// Transmits a command fragment by fragment, waiting for continuation requests where required.
+///
+/// See the [module documentation](self) for details.
+#[derive(Clone, Debug)]
+pub struct CommandTransmitter {
+    fragments: Encoded,
+    /// Synchronizing literal that is sent after the next continuation request.
+    literal: Option<Vec<u8>>,
+    awaiting_continuation: bool,
+    /// All fragments were consumed.
+    exhausted: bool,
+}
+
+/// Error produced by [`CommandTransmitter::continuation`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransmitError {
+    /// A continuation request was received although no literal was announced.
+    UnexpectedContinuation,
+}
+
+impl CommandTransmitter {
+    /// Create a transmitter for `command` using the default [`CommandCodec`].
+    pub fn new(command: &Command) -> Self {
+        Self::from(CommandCodec::default().encode(command))
+    }
+
+    /// Returns `true` when a synchronizing literal was announced and the server's continuation
+    /// request is required to proceed.
+    pub fn awaiting_continuation(&self) -> bool {
+        self.awaiting_continuation
+    }
+
+    /// Returns `true` when the command was sent completely.
+    pub fn is_done(&self) -> bool {
+        self.exhausted && !self.awaiting_continuation && self.literal.is_none()
+    }
+
+    /// Returns all bytes that can be sent without waiting for the server.
+    ///
+    /// Returns `None` when the transmitter is [awaiting a continuation request](Self::awaiting_continuation)
+    /// or when the command was sent completely.
+    pub fn next_bytes(&mut self) -> Option<Vec<u8>> {
+        if self.awaiting_continuation {
+            return None;
+        }
+
+        let mut out = self.literal.take().unwrap_or_default();
+
+        self.exhausted = true;
+
+        for fragment in self.fragments.by_ref() {
+            match fragment {
+                Fragment::Line { mut data }
+                | Fragment::Literal {
+                    mut data,
+                    mode: LiteralMode::NonSync,
+                } => out.append(&mut data),
+                Fragment::Literal {
+                    data,
+                    mode: LiteralMode::Sync,
+                } => {
+                    self.literal = Some(data);
+                    self.awaiting_continuation = true;
+                    self.exhausted = false;
+                    break;
+                }
+            }
+        }
+
+        if out.is_empty() {
+            None
+        } else {
+            Some(out)
+        }
+    }
+
+    /// Accept the server's continuation request for the announced literal.
+    pub fn continuation(
+        &mut self,
+        _continuation: &CommandContinuationRequest,
+    ) -> Result<(), TransmitError> {
+        if !self.awaiting_continuation {
+            return Err(TransmitError::UnexpectedContinuation);
+        }
+
+        self.awaiting_continuation = false;
+
+        Ok(())
+    }
+}
+
+impl From<Encoded> for CommandTransmitter {
+    fn from(fragments: Encoded) -> Self {
+        Self {
+            fragments,
+            literal: None,
+            awaiting_continuation: false,
+            exhausted: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use imap_types::{
+        command::CommandBody,
+        core::{AString, Literal},
+        response::Response,
+    };
+
+    use super::*;
+    use crate::{decode::Decoder, ResponseCodec};
+
+    fn continuation() -> CommandContinuationRequest<'static> {
+        let (_, response) = ResponseCodec::default().decode(b"+ Ready\r\n").unwrap();
+
+        match response {
+            Response::CommandContinuationRequest(continuation) => continuation,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_transmit_sync_literals() {
+        let command = Command::new(
+            "A",
+            CommandBody::login(
+                Literal::try_from("alice").unwrap(),
+                AString::from(Literal::try_from("").unwrap()),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let mut transmitter = CommandTransmitter::new(&command);
+
+        assert!(!transmitter.awaiting_continuation());
+        assert_eq!(transmitter.next_bytes().unwrap(), b"A LOGIN {5}\r\n");
+        assert!(transmitter.awaiting_continuation());
+        assert!(!transmitter.is_done());
+        assert_eq!(transmitter.next_bytes(), None);
+
+        transmitter.continuation(&continuation()).unwrap();
+        assert_eq!(transmitter.next_bytes().unwrap(), b"alice {0}\r\n");
+        assert!(transmitter.awaiting_continuation());
+
+        // The empty literal must be confirmed, too.
+        transmitter.continuation(&continuation()).unwrap();
+        assert_eq!(transmitter.next_bytes().unwrap(), b"\r\n");
+        assert!(transmitter.is_done());
+        assert_eq!(transmitter.next_bytes(), None);
+        assert_eq!(
+            transmitter.continuation(&continuation()),
+            Err(TransmitError::UnexpectedContinuation)
+        );
+    }
+
+    #[test]
+    fn test_transmit_non_sync_literal() {
+        let command = Command::new(
+            "A",
+            CommandBody::login(
+                Literal::try_from("alice").unwrap().into_non_sync(),
+                "password",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let mut transmitter = CommandTransmitter::new(&command);
+
+        assert_eq!(
+            transmitter.next_bytes().unwrap(),
+            b"A LOGIN {5+}\r\nalice password\r\n"
+        );
+        assert!(!transmitter.awaiting_continuation());
+        assert!(transmitter.is_done());
+        assert_eq!(transmitter.next_bytes(), None);
+    }
+
+    #[test]
+    fn test_transmit_without_literal() {
+        let mut transmitter =
+            CommandTransmitter::new(&Command::new("A", CommandBody::Noop).unwrap());
+
+        assert_eq!(
+            transmitter.continuation(&continuation()),
+            Err(TransmitError::UnexpectedContinuation)
+        );
+        assert_eq!(transmitter.next_bytes().unwrap(), b"A NOOP\r\n");
+        assert!(transmitter.is_done());
+    }
+}
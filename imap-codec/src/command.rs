@@ -25,10 +25,14 @@ use nom::{
 
 #[cfg(feature = "ext_binary")]
 use crate::extensions::binary::literal8;
+#[cfg(feature = "ext_gmail")]
+use crate::extensions::gmail::store_gmail_labels;
 #[cfg(feature = "ext_id")]
 use crate::extensions::id::id;
 #[cfg(feature = "ext_metadata")]
 use crate::extensions::metadata::{getmetadata, setmetadata};
+#[cfg(feature = "ext_replace")]
+use crate::extensions::replace::replace;
 #[cfg(feature = "ext_uidplus")]
 use crate::extensions::uidplus::uid_expunge;
 #[cfg(feature = "ext_sort_thread")]
@@ -40,10 +44,12 @@ use crate::{
     decode::{IMAPErrorKind, IMAPResult},
     extensions::{
         compress::compress,
+        condstore_qresync::select_params,
         enable::enable,
         idle::idle,
         quota::{getquota, getquotaroot, setquota},
         r#move::r#move,
+        special_use::create_params,
     },
     fetch::fetch_att,
     flag::{flag, flag_list},
@@ -193,15 +199,23 @@ pub(crate) fn append(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
     ))
 }
 
-/// `create = "CREATE" SP mailbox`
+/// ```abnf
+/// create = "CREATE" SP mailbox [create-params] ; create-params defined in RFC 4466
+/// ```
 ///
 /// Note: Use of INBOX gives a NO error
 pub(crate) fn create(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
-    let mut parser = tuple((tag_no_case(b"CREATE"), sp, mailbox));
+    let mut parser = tuple((tag_no_case(b"CREATE"), sp, mailbox, opt(create_params)));
 
-    let (remaining, (_, _, mailbox)) = parser(input)?;
+    let (remaining, (_, _, mailbox, parameters)) = parser(input)?;
 
-    Ok((remaining, CommandBody::Create { mailbox }))
+    Ok((
+        remaining,
+        CommandBody::Create {
+            mailbox,
+            parameters: parameters.unwrap_or_default(),
+        },
+    ))
 }
 
 /// `delete = "DELETE" SP mailbox`
@@ -215,13 +229,21 @@ pub(crate) fn delete(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
     Ok((remaining, CommandBody::Delete { mailbox }))
 }
 
-/// `examine = "EXAMINE" SP mailbox`
+/// ```abnf
+/// examine = "EXAMINE" SP mailbox [select-params]
+/// ```
 pub(crate) fn examine(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
-    let mut parser = tuple((tag_no_case(b"EXAMINE"), sp, mailbox));
+    let mut parser = tuple((tag_no_case(b"EXAMINE"), sp, mailbox, opt(select_params)));
 
-    let (remaining, (_, _, mailbox)) = parser(input)?;
+    let (remaining, (_, _, mailbox, parameters)) = parser(input)?;
 
-    Ok((remaining, CommandBody::Examine { mailbox }))
+    Ok((
+        remaining,
+        CommandBody::Examine {
+            mailbox,
+            parameters: parameters.unwrap_or_default(),
+        },
+    ))
 }
 
 /// `list = "LIST" SP mailbox SP list-mailbox`
@@ -271,13 +293,21 @@ pub(crate) fn rename(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
     ))
 }
 
-/// `select = "SELECT" SP mailbox`
+/// ```abnf
+/// select = "SELECT" SP mailbox [select-params]
+/// ```
 pub(crate) fn select(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
-    let mut parser = tuple((tag_no_case(b"SELECT"), sp, mailbox));
+    let mut parser = tuple((tag_no_case(b"SELECT"), sp, mailbox, opt(select_params)));
 
-    let (remaining, (_, _, mailbox)) = parser(input)?;
+    let (remaining, (_, _, mailbox, parameters)) = parser(input)?;
 
-    Ok((remaining, CommandBody::Select { mailbox }))
+    Ok((
+        remaining,
+        CommandBody::Select {
+            mailbox,
+            parameters: parameters.unwrap_or_default(),
+        },
+    ))
 }
 
 /// `status = "STATUS" SP mailbox SP "(" status-att *(SP status-att) ")"`
@@ -426,6 +456,8 @@ pub(crate) fn command_select(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
         copy,
         fetch,
         store,
+        #[cfg(feature = "ext_gmail")]
+        store_gmail_labels,
         uid,
         search,
         #[cfg(feature = "ext_sort_thread")]
@@ -434,6 +466,8 @@ pub(crate) fn command_select(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
         thread,
         value(CommandBody::Unselect, tag_no_case(b"UNSELECT")),
         r#move,
+        #[cfg(feature = "ext_replace")]
+        replace,
     ))(input)
 }
 
@@ -554,7 +588,17 @@ pub(crate) fn uid(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
     let mut parser = tuple((
         tag_no_case(b"UID"),
         sp,
-        alt((copy, fetch, search, store, r#move)),
+        alt((
+            copy,
+            fetch,
+            search,
+            store,
+            r#move,
+            #[cfg(feature = "ext_replace")]
+            replace,
+            #[cfg(feature = "ext_gmail")]
+            store_gmail_labels,
+        )),
     ));
 
     let (remaining, (_, _, mut cmd)) = parser(input)?;
@@ -565,6 +609,10 @@ pub(crate) fn uid(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
         | CommandBody::Search { ref mut uid, .. }
         | CommandBody::Store { ref mut uid, .. }
         | CommandBody::Move { ref mut uid, .. } => *uid = true,
+        #[cfg(feature = "ext_replace")]
+        CommandBody::Replace { ref mut uid, .. } => *uid = true,
+        #[cfg(feature = "ext_gmail")]
+        CommandBody::StoreGmailLabels { ref mut uid, .. } => *uid = true,
         _ => unreachable!(),
     }
 
@@ -578,10 +626,15 @@ mod tests {
     use imap_types::{
         core::Tag,
         fetch::{MessageDataItemName, Section},
+        mailbox::ListMailbox,
     };
 
     use super::*;
-    use crate::{encode::Encoder, CommandCodec};
+    use crate::{
+        encode::Encoder,
+        testing::{kat_inverse_command, known_answer_test_encode},
+        CommandCodec,
+    };
 
     #[test]
     fn test_parse_fetch() {
@@ -645,6 +698,80 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_kat_inverse_command_list_lsub() {
+        kat_inverse_command(&[
+            (
+                b"A LIST \"\" *\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new("A", CommandBody::list("", "*").unwrap()).unwrap(),
+            ),
+            (
+                b"A LIST \"\" \"\"\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::list("", ListMailbox::String("".try_into().unwrap())).unwrap(),
+                )
+                .unwrap(),
+            ),
+            (
+                b"A LSUB \"\" *\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new("A", CommandBody::lsub("", "*").unwrap()).unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_encode_command_list_lsub_patterns() {
+        let tests = [
+            (CommandBody::list("", "%").unwrap(), b"LIST \"\" %".as_ref()),
+            (CommandBody::list("", "*").unwrap(), b"LIST \"\" *"),
+            (
+                CommandBody::list("", "INBOX.*").unwrap(),
+                b"LIST \"\" INBOX.*",
+            ),
+            (
+                CommandBody::list("#news.", "comp.mail.%").unwrap(),
+                b"LIST #news. comp.mail.%",
+            ),
+            (
+                CommandBody::list("", "My Folder/%").unwrap(),
+                b"LIST \"\" \"My Folder/%\"",
+            ),
+            (
+                CommandBody::list("Other Users", "a\"b*").unwrap(),
+                b"LIST \"Other Users\" \"a\\\"b*\"",
+            ),
+            (CommandBody::lsub("inbox", "%").unwrap(), b"LSUB INBOX %"),
+        ];
+
+        for test in tests {
+            known_answer_test_encode(test);
+        }
+    }
+
+    #[test]
+    fn test_kat_inverse_command_copy() {
+        kat_inverse_command(&[
+            (
+                b"A COPY 2:4 MEETING\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new("A", CommandBody::copy("2:4", "MEETING", false).unwrap()).unwrap(),
+            ),
+            (
+                b"A UID COPY 1,3:* \"Saved Items\"\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::copy("1,3:*", "Saved Items", true).unwrap(),
+                )
+                .unwrap(),
+            ),
+        ]);
+    }
+
     #[test]
     fn test_that_empty_ir_is_encoded_correctly() {
         let command = Command::new(
@@ -1,4 +1,8 @@
-use std::{borrow::Cow, num::NonZeroU32, str::from_utf8};
+use std::{
+    borrow::Cow,
+    num::{NonZeroU32, NonZeroU64},
+    str::from_utf8,
+};
 
 #[cfg(not(feature = "quirk_crlf_relaxed"))]
 use abnf_core::streaming::crlf;
@@ -20,7 +24,7 @@ use nom::{
     branch::alt,
     bytes::streaming::{escaped, tag, tag_no_case, take, take_while, take_while1, take_while_m_n},
     character::streaming::{char, digit1, one_of},
-    combinator::{map, map_res, opt, recognize},
+    combinator::{map, map_res, opt, recognize, verify},
     sequence::{delimited, terminated, tuple},
 };
 
@@ -31,6 +35,8 @@ use crate::decode::{IMAPErrorKind, IMAPParseError, IMAPResult};
 /// `number = 1*DIGIT`
 ///
 /// Unsigned 32-bit integer (0 <= n < 4,294,967,296)
+///
+/// Note: The ABNF permits leading zeros, i.e., `007` is parsed as `7`.
 pub(crate) fn number(input: &[u8]) -> IMAPResult<&[u8], u32> {
     map_res(
         // # Safety
@@ -58,11 +64,37 @@ pub(crate) fn number64(input: &[u8]) -> IMAPResult<&[u8], u64> {
     )(input)
 }
 
+/// ```abnf
+/// mod-sequence-value = 1*DIGIT
+/// ```
+///
+/// Positive unsigned 63-bit integer (mod-sequence) (1 <= n <= 9,223,372,036,854,775,807)
+///
+/// Defined in RFC 7162
+pub(crate) fn mod_sequence_value(input: &[u8]) -> IMAPResult<&[u8], NonZeroU64> {
+    map_res(number64, NonZeroU64::try_from)(input)
+}
+
 /// `nz-number = digit-nz *DIGIT`
 ///
 /// Non-zero unsigned 32-bit integer (0 < n < 4,294,967,296)
+///
+/// Note: Other than `number`, the ABNF forbids leading zeros, i.e., `0`, `00`, and `007` are rejected.
 pub(crate) fn nz_number(input: &[u8]) -> IMAPResult<&[u8], NonZeroU32> {
-    map_res(number, NonZeroU32::try_from)(input)
+    let (remaining, number) = number(input)?;
+
+    // `number` succeeded, thus, `input` starts with a digit.
+    if input[0] == b'0' {
+        return Err(nom::Err::Error(IMAPParseError {
+            input,
+            kind: IMAPErrorKind::BadNumber,
+        }));
+    }
+
+    // # Safety
+    //
+    // `unwrap` is safe because the first digit is not `0`.
+    Ok((remaining, NonZeroU32::new(number).unwrap()))
 }
 
 // ----- string -----
@@ -239,6 +271,9 @@ pub(crate) fn nil(input: &[u8]) -> IMAPResult<&[u8], &[u8]> {
 // ----- text -----
 
 /// `text = 1*TEXT-CHAR`
+///
+/// `TEXT-CHAR` is any `CHAR` (`%x01-7F`) except CR and LF. Thus, the parser stops right before
+/// CRLF, and control characters such as TAB are accepted. A NUL also ends the text.
 pub(crate) fn text(input: &[u8]) -> IMAPResult<&[u8], Text> {
     map(take_while1(is_text_char), |bytes|
         // # Safety
@@ -251,6 +286,9 @@ pub(crate) fn text(input: &[u8]) -> IMAPResult<&[u8], Text> {
 // ----- base64 -----
 
 /// `base64 = *(4base64-char) [base64-terminal]`
+///
+/// Decoding is strict: Padding is required, and pad bits must be zero. Thus, there is exactly one
+/// encoding for every decoded value, and re-encoding always yields the original input.
 pub(crate) fn base64(input: &[u8]) -> IMAPResult<&[u8], Vec<u8>> {
     map_res(
         recognize(tuple((
@@ -273,8 +311,16 @@ pub(crate) fn is_base64_char(i: u8) -> bool {
 /// `charset = atom / quoted`
 ///
 /// Note: see errata id: 261
+///
+/// Note: An empty quoted charset (`""`) is rejected.
 pub(crate) fn charset(input: &[u8]) -> IMAPResult<&[u8], Charset> {
-    alt((map(atom, Charset::Atom), map(quoted, Charset::Quoted)))(input)
+    alt((
+        map(atom, Charset::Atom),
+        map(
+            verify(quoted, |quoted: &Quoted| !quoted.as_ref().is_empty()),
+            Charset::Quoted,
+        ),
+    ))(input)
 }
 
 // ----- tag -----
@@ -291,6 +337,8 @@ pub(crate) fn tag_imap(input: &[u8]) -> IMAPResult<&[u8], Tag> {
 
 #[cfg(test)]
 mod tests {
+    use imap_types::response::CommandContinuationRequest;
+
     use super::*;
     use crate::encode::{EncodeContext, EncodeIntoContext};
 
@@ -308,6 +356,44 @@ mod tests {
         assert_eq!(rem, b" yyy");
     }
 
+    #[test]
+    fn test_text() {
+        let tests = [
+            (b"hello\r\n".as_ref(), "hello", b"\r\n".as_ref()),
+            (b"a\tb\x01c\x7f\r\n", "a\tb\x01c\x7f", b"\r\n"),
+            (b"[ not a code ]\r\n", "[ not a code ]", b"\r\n"),
+            (b"a [b\r\n", "a [b", b"\r\n"),
+            (b"bare\rcr", "bare", b"\rcr"),
+            (b"bare\nlf", "bare", b"\nlf"),
+            (b"nul\x00\r\n", "nul", b"\x00\r\n"),
+        ];
+
+        for (test, expected, expected_rem) in tests {
+            let (rem, got) = text(test).unwrap();
+            assert_eq!(got.as_ref(), expected);
+            assert_eq!(rem, expected_rem);
+        }
+
+        assert!(text(b"\r\n").is_err());
+        assert!(text(b"\x80\r\n").is_err());
+    }
+
+    #[test]
+    fn test_atom_stops_at_atom_specials() {
+        let specials = b"(){ \x00\x01\t\r\n\x1f\x7f%*\"\\]\x80\xff";
+
+        for &special in specials {
+            // Stops before the special ...
+            let input = [b'a', special, b'?'];
+            let (rem, val) = atom(&input).unwrap();
+            assert_eq!(val, "a".try_into().unwrap());
+            assert_eq!(rem, &input[1..]);
+
+            // ... and does not start with it.
+            assert!(matches!(atom(&input[1..]), Err(nom::Err::Error(_))));
+        }
+    }
+
     #[test]
     fn test_quoted() {
         let (rem, val) = quoted(br#""Hello"???"#).unwrap();
@@ -342,6 +428,58 @@ mod tests {
         assert!(matches!(quoted(br#"\"#), Err(nom::Err::Error(_))));
     }
 
+    #[test]
+    fn test_quoted_escaping_roundtrip() {
+        let (rem, val) = quoted(br#""a\\b\"c"???"#).unwrap();
+        assert_eq!(rem, b"???");
+        assert_eq!(val.as_ref(), "a\\b\"c");
+
+        let mut ctx = EncodeContext::new();
+        val.encode_ctx(&mut ctx).unwrap();
+        assert_eq!(ctx.dump(), br#""a\\b\"c""#);
+
+        // Only `\"` and `\\` are valid escapes.
+        assert!(quoted(br#""a\nb""#).is_err());
+        assert!(quoted(br#""a\'b""#).is_err());
+    }
+
+    #[test]
+    fn test_quoted_rejects_cr_lf() {
+        let tests = [
+            b"\"a\rb\"".as_ref(),
+            b"\"a\nb\"".as_ref(),
+            b"\"a\r\n\"".as_ref(),
+            b"\"\\\r\"".as_ref(),
+        ];
+
+        for test in tests {
+            assert!(matches!(quoted(test), Err(nom::Err::Error(_))));
+        }
+
+        assert!(Quoted::try_from("a\rb").is_err());
+        assert!(Quoted::try_from("a\nb").is_err());
+    }
+
+    #[test]
+    fn test_string_roundtrip_preserves_form() {
+        let tests = [
+            (br#""Hello \"World\" \\o/""#.as_ref(), false),
+            (b"{5}\r\na\x01\tb\x7f".as_ref(), true),
+            // Could be quoted, but was sent as a literal.
+            (b"{5}\r\nHello".as_ref(), true),
+        ];
+
+        for (test, is_literal) in tests {
+            let (rem, val) = string(test).unwrap();
+            assert!(rem.is_empty());
+            assert_eq!(matches!(val, IString::Literal(_)), is_literal);
+
+            let mut ctx = EncodeContext::new();
+            val.encode_ctx(&mut ctx).unwrap();
+            assert_eq!(ctx.dump(), test);
+        }
+    }
+
     #[test]
     fn test_quoted_char() {
         let (rem, val) = quoted_char(b"\\\"xxx").unwrap();
@@ -369,6 +507,25 @@ mod tests {
         assert!(nz_number(b"999?").is_ok());
     }
 
+    #[test]
+    fn test_number_leading_zeros() {
+        // `number = 1*DIGIT` permits leading zeros ...
+        assert_eq!(number(b"0?").unwrap(), (b"?".as_ref(), 0));
+        assert_eq!(number(b"00?").unwrap(), (b"?".as_ref(), 0));
+        assert_eq!(number(b"007?").unwrap(), (b"?".as_ref(), 7));
+        assert_eq!(number64(b"007?").unwrap(), (b"?".as_ref(), 7));
+
+        // ... but `nz-number = digit-nz *DIGIT` doesn't.
+        for test in [b"0?".as_ref(), b"00?", b"007?", b"0100?"] {
+            assert!(matches!(nz_number(test), Err(nom::Err::Error(_))));
+        }
+        assert_eq!(
+            nz_number(b"100?").unwrap(),
+            (b"?".as_ref(), NonZeroU32::new(100).unwrap())
+        );
+        assert!(matches!(nz_number(b"0"), Err(nom::Err::Incomplete(_))));
+    }
+
     #[test]
     fn test_literal() {
         assert!(literal(b"{3}\r\n123").is_ok());
@@ -379,6 +536,27 @@ mod tests {
         assert_eq!(val, Literal::try_from(b"123".as_slice()).unwrap());
     }
 
+    #[test]
+    fn test_literal_length_consistency() {
+        let data = [b'x'; 100];
+
+        // Exactly the announced length.
+        let input = [b"{100}\r\n".as_ref(), &data].concat();
+        let (rem, val) = literal(&input).unwrap();
+        assert!(rem.is_empty());
+        assert_eq!(val.data(), data);
+
+        // Fewer bytes than announced (including bytes that look like CRLF).
+        let input = [b"{100}\r\n".as_ref(), &data[..50], b"\r\n"].concat();
+        assert!(matches!(literal(&input), Err(nom::Err::Incomplete(_))));
+
+        // More bytes than announced belong to the next token.
+        let input = [b"{100}\r\n".as_ref(), &data, b"x)\r\n"].concat();
+        let (rem, val) = literal(&input).unwrap();
+        assert_eq!(rem, b"x)\r\n");
+        assert_eq!(val.data(), data);
+    }
+
     #[test]
     fn test_nil() {
         assert!(nil(b"nil").is_ok());
@@ -395,7 +573,6 @@ mod tests {
         let tests = [
             ("bengali", "bengali"),
             ("\"simple\" english", r#""\"simple\" english""#),
-            ("", "\"\""),
             ("\"", "\"\\\"\""),
             ("\\", "\"\\\\\""),
         ];
@@ -411,6 +588,7 @@ mod tests {
             assert_eq!(from_utf8(&out).unwrap(), *expected);
         }
 
+        assert!(Charset::try_from("").is_err());
         assert!(Charset::try_from("\r").is_err());
         assert!(Charset::try_from("\n").is_err());
         assert!(Charset::try_from("¹").is_err());
@@ -418,6 +596,52 @@ mod tests {
         assert!(Charset::try_from("\x00").is_err());
     }
 
+    #[test]
+    fn test_parse_charset() {
+        let (rem, val) = charset(b"utf-8 ").unwrap();
+        assert_eq!(rem, b" ");
+        assert_eq!(val, Charset::try_from("utf-8").unwrap());
+
+        let (rem, val) = charset(b"\"UTF 8\" ").unwrap();
+        assert_eq!(rem, b" ");
+        assert_eq!(val, Charset::try_from("UTF 8").unwrap());
+
+        assert!(charset(b"\"\" ").is_err());
+    }
+
+    #[test]
+    fn test_tag_imap() {
+        let (rem, val) = tag_imap(b"A1 NOOP").unwrap();
+        assert_eq!(val, Tag::try_from("A1").unwrap());
+        assert_eq!(rem, b" NOOP");
+
+        // `tag_imap` stops before `+`, i.e., the command parser sees `+1` instead of `SP`.
+        let (rem, val) = tag_imap(b"A+1 NOOP").unwrap();
+        assert_eq!(val, Tag::try_from("A").unwrap());
+        assert_eq!(rem, b"+1 NOOP");
+        assert!(crate::command::command(b"A+1 NOOP\r\n").is_err());
+
+        for test in [
+            b"+ ".as_ref(),
+            b"+1 ",
+            b" A",
+            b"* ",
+            b"% ",
+            b"( ",
+            b"{ ",
+            b"\" ",
+            b"\\ ",
+        ] {
+            assert!(tag_imap(test).is_err());
+        }
+
+        for test in [
+            "", "A+1", "+", "A 1", "*", "A%", "A(", "A{", "A\"", "A\\", "A\r\n",
+        ] {
+            assert!(Tag::try_from(test).is_err());
+        }
+    }
+
     #[test]
     fn test_is_base64_char() {
         assert!(is_base64_char(b'a'));
@@ -433,4 +657,51 @@ mod tests {
         //_base64.decode(b"aa==").unwrap();
         _base64.decode(b"aQ==").unwrap();
     }
+
+    #[test]
+    fn test_base64_strict() {
+        // Properly padded
+        let tests = [
+            (b"\r\n".as_ref(), b"".as_ref()),
+            (b"aGVsbG8=\r\n", b"hello"),
+            (b"aGVsbG8h\r\n", b"hello!"),
+            (b"aGVsbA==\r\n", b"hell"),
+        ];
+
+        for (test, expected) in tests {
+            let (rem, got) = base64(test).unwrap();
+            assert_eq!(rem, b"\r\n");
+            assert_eq!(got, expected);
+            assert_eq!(_base64.encode(&got).as_bytes(), &test[..test.len() - 2]);
+        }
+
+        // Unpadded, wrongly padded, or non-zero pad bits
+        let tests = [
+            b"aGVsbG8\r\n".as_ref(),
+            b"aGVsbA\r\n",
+            b"aGVsbA=\r\n",
+            b"aGVsbG8==\r\n",
+            b"aa==\r\n",
+        ];
+
+        for test in tests {
+            assert!(matches!(
+                base64(test),
+                Err(nom::Err::Error(IMAPParseError {
+                    kind: IMAPErrorKind::BadBase64,
+                    ..
+                }))
+            ));
+        }
+
+        // Invalid characters are rejected, not truncated.
+        for test in [b"aGVs*bG8=\r\n".as_ref(), b"aGVsb*G8=\r\n", b"aGVsbG8*\r\n"] {
+            assert!(crate::auth::authenticate_data(test).is_err());
+
+            let continue_req = [b"+ ".as_ref(), test].concat();
+            let (rem, got) = crate::response::continue_req(&continue_req).unwrap();
+            assert!(rem.is_empty());
+            assert!(matches!(got, CommandContinuationRequest::Basic(_)));
+        }
+    }
 }
@@ -239,7 +239,10 @@ pub(crate) fn addr_host(input: &[u8]) -> IMAPResult<&[u8], NString> {
 
 #[cfg(test)]
 mod tests {
-    use imap_types::core::{IString, NString};
+    use imap_types::{
+        core::{IString, NString},
+        response::{Data, Response},
+    };
 
     use super::*;
 
@@ -259,4 +262,58 @@ mod tests {
         );
         assert_eq!(rem, b"");
     }
+
+    #[test]
+    fn test_parse_address_nil_vs_empty() {
+        let (rem, val) = address(b"(\"\" NIL \"alice\" \"example.org\")").unwrap();
+        assert_eq!(rem, b"");
+
+        assert!(!val.name.is_nil());
+        assert_eq!(val.name.as_bytes(), Some(b"".as_ref()));
+        assert!(val.adl.is_nil());
+        assert_eq!(val.adl.as_bytes(), None);
+    }
+
+    #[test]
+    fn test_parse_fetch_envelope_with_group() {
+        let (rem, val) = crate::response::response(
+            b"* 1 FETCH (ENVELOPE (NIL \"Party\" \
+((\"Alice\" NIL \"alice\" \"example.com\")) NIL NIL \
+((NIL NIL \"Friends\" NIL)(NIL NIL \"bob\" \"example.com\")(\"Carol\" NIL \"carol\" \"example.com\")(NIL NIL NIL NIL)\
+(NIL NIL \"undisclosed-recipients\" NIL)(NIL NIL NIL NIL)) \
+NIL NIL NIL NIL))\r\n",
+        )
+        .unwrap();
+        assert_eq!(rem, b"");
+
+        let envelope = match val {
+            Response::Data(Data::Fetch { items, .. }) => items.envelope().unwrap().clone(),
+            _ => unreachable!(),
+        };
+
+        let markers = envelope
+            .to
+            .iter()
+            .map(|address| (address.mailbox.is_nil(), address.host.is_nil()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            markers,
+            [
+                // Group start
+                (false, true),
+                (false, false),
+                (false, false),
+                // Group end
+                (true, true),
+                // Empty group
+                (false, true),
+                (true, true),
+            ]
+        );
+
+        assert_eq!(
+            Address::list_to_rfc5322(&envelope.to),
+            "Friends: bob@example.com, Carol <carol@example.com>;, undisclosed-recipients:;"
+        );
+    }
 }
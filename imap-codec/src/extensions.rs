@@ -1,7 +1,10 @@
 #[cfg(feature = "ext_binary")]
 pub mod binary;
 pub mod compress;
+pub mod condstore_qresync;
 pub mod enable;
+#[cfg(feature = "ext_gmail")]
+pub mod gmail;
 #[cfg(feature = "ext_id")]
 pub mod id;
 pub mod idle;
@@ -9,9 +12,14 @@ pub mod literal;
 #[cfg(feature = "ext_metadata")]
 pub mod metadata;
 pub mod r#move;
+#[cfg(feature = "ext_preview")]
+pub mod preview;
 pub mod quota;
+#[cfg(feature = "ext_replace")]
+pub mod replace;
 #[cfg(feature = "ext_sort_thread")]
 pub mod sort;
+pub mod special_use;
 #[cfg(feature = "ext_sort_thread")]
 pub mod thread;
 #[cfg(feature = "ext_uidplus")]
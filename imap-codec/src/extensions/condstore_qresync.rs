@@ -0,0 +1,254 @@
+//! The IMAP CONDSTORE and QRESYNC Extensions
+//!
+//! Select parameters are encoded and parsed in every build.
+
+use std::io::Write;
+
+use abnf_core::streaming::sp;
+use imap_types::extensions::condstore_qresync::SelectParameter;
+use nom::{
+    branch::alt,
+    bytes::streaming::{tag, tag_no_case},
+    combinator::{map, opt, value},
+    multi::separated_list1,
+    sequence::{delimited, preceded, separated_pair, tuple},
+};
+
+use crate::{
+    core::{mod_sequence_value, nz_number},
+    decode::IMAPResult,
+    encode::{EncodeContext, EncodeIntoContext},
+    sequence::sequence_set,
+};
+
+/// ```abnf
+/// select-params = SP "(" select-param *(SP select-param) ")"
+/// ```
+///
+/// Defined in RFC 4466
+pub(crate) fn select_params(input: &[u8]) -> IMAPResult<&[u8], Vec<SelectParameter>> {
+    preceded(
+        sp,
+        delimited(tag(b"("), separated_list1(sp, select_param), tag(b")")),
+    )(input)
+}
+
+/// ```abnf
+/// select-param =/ condstore-param
+///
+/// condstore-param = "CONDSTORE"
+///
+/// select-param =/ "QRESYNC" SP "("
+///                  uidvalidity SP
+///                  mod-sequence-value [SP known-uids]
+///                  [SP seq-match-data] ")"
+///
+/// known-uids = sequence-set
+///
+/// seq-match-data = "(" known-sequence-set SP known-uid-set ")"
+/// ```
+///
+/// Defined in RFC 7162
+pub(crate) fn select_param(input: &[u8]) -> IMAPResult<&[u8], SelectParameter> {
+    alt((
+        value(SelectParameter::CondStore, tag_no_case(b"CONDSTORE")),
+        map(
+            tuple((
+                tag_no_case(b"QRESYNC"),
+                sp,
+                tag(b"("),
+                nz_number,
+                sp,
+                mod_sequence_value,
+                opt(preceded(sp, sequence_set)),
+                opt(preceded(
+                    sp,
+                    delimited(
+                        tag(b"("),
+                        separated_pair(sequence_set, sp, sequence_set),
+                        tag(b")"),
+                    ),
+                )),
+                tag(b")"),
+            )),
+            |(_, _, _, uid_validity, _, mod_sequence_value, known_uids, seq_match_data, _)| {
+                SelectParameter::QResync {
+                    uid_validity,
+                    mod_sequence_value,
+                    known_uids,
+                    seq_match_data,
+                }
+            },
+        ),
+    ))(input)
+}
+
+impl EncodeIntoContext for SelectParameter {
+    fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
+        match self {
+            Self::CondStore => ctx.write_all(b"CONDSTORE"),
+            Self::QResync {
+                uid_validity,
+                mod_sequence_value,
+                known_uids,
+                seq_match_data,
+            } => {
+                write!(ctx, "QRESYNC ({uid_validity} {mod_sequence_value}")?;
+
+                if let Some(known_uids) = known_uids {
+                    ctx.write_all(b" ")?;
+                    known_uids.encode_ctx(ctx)?;
+                }
+
+                if let Some((known_sequence_set, known_uid_set)) = seq_match_data {
+                    ctx.write_all(b" (")?;
+                    known_sequence_set.encode_ctx(ctx)?;
+                    ctx.write_all(b" ")?;
+                    known_uid_set.encode_ctx(ctx)?;
+                    ctx.write_all(b")")?;
+                }
+
+                ctx.write_all(b")")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::{NonZeroU32, NonZeroU64};
+
+    use imap_types::{
+        command::{Command, CommandBody},
+        mailbox::Mailbox,
+        sequence::SequenceSet,
+    };
+
+    use super::*;
+    use crate::testing::{kat_inverse_command, known_answer_test_encode};
+
+    #[test]
+    fn test_kat_inverse_command_select_parameters() {
+        kat_inverse_command(&[
+            (
+                b"A SELECT INBOX\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new("A", CommandBody::select("inbox").unwrap()).unwrap(),
+            ),
+            (
+                b"A SELECT INBOX (CONDSTORE)\r\n?",
+                b"?",
+                Command::new(
+                    "A",
+                    CommandBody::Select {
+                        mailbox: Mailbox::Inbox,
+                        parameters: vec![SelectParameter::CondStore],
+                    },
+                )
+                .unwrap(),
+            ),
+            (
+                b"A EXAMINE INBOX (CONDSTORE)\r\n",
+                b"",
+                Command::new(
+                    "A",
+                    CommandBody::Examine {
+                        mailbox: Mailbox::Inbox,
+                        parameters: vec![SelectParameter::CondStore],
+                    },
+                )
+                .unwrap(),
+            ),
+            (
+                b"A SELECT INBOX (QRESYNC (67890007 20050715194045000 41,43:211,214:541) CONDSTORE)\r\n",
+                b"",
+                Command::new(
+                    "A",
+                    CommandBody::Select {
+                        mailbox: Mailbox::Inbox,
+                        parameters: vec![
+                            SelectParameter::QResync {
+                                uid_validity: NonZeroU32::new(67890007).unwrap(),
+                                mod_sequence_value: NonZeroU64::new(20050715194045000).unwrap(),
+                                known_uids: Some(
+                                    SequenceSet::try_from("41,43:211,214:541").unwrap(),
+                                ),
+                                seq_match_data: None,
+                            },
+                            SelectParameter::CondStore,
+                        ],
+                    },
+                )
+                .unwrap(),
+            ),
+            (
+                b"A SELECT INBOX (QRESYNC (1 2 (1:5 10:14)))\r\n",
+                b"",
+                Command::new(
+                    "A",
+                    CommandBody::Select {
+                        mailbox: Mailbox::Inbox,
+                        parameters: vec![SelectParameter::QResync {
+                            uid_validity: NonZeroU32::new(1).unwrap(),
+                            mod_sequence_value: NonZeroU64::new(2).unwrap(),
+                            known_uids: None,
+                            seq_match_data: Some((
+                                SequenceSet::try_from("1:5").unwrap(),
+                                SequenceSet::try_from("10:14").unwrap(),
+                            )),
+                        }],
+                    },
+                )
+                .unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_encode_select_parameters() {
+        let tests = [
+            (
+                CommandBody::select("INBOX").unwrap(),
+                b"SELECT INBOX".as_ref(),
+            ),
+            (
+                CommandBody::Select {
+                    mailbox: Mailbox::try_from("Drafts").unwrap(),
+                    parameters: vec![SelectParameter::CondStore],
+                },
+                b"SELECT Drafts (CONDSTORE)",
+            ),
+            (
+                CommandBody::Examine {
+                    mailbox: Mailbox::Inbox,
+                    parameters: vec![],
+                },
+                b"EXAMINE INBOX",
+            ),
+            (
+                CommandBody::Examine {
+                    mailbox: Mailbox::Inbox,
+                    parameters: vec![SelectParameter::QResync {
+                        uid_validity: NonZeroU32::new(1).unwrap(),
+                        mod_sequence_value: NonZeroU64::new(2).unwrap(),
+                        known_uids: Some(SequenceSet::try_from("1:*").unwrap()),
+                        seq_match_data: None,
+                    }],
+                },
+                b"EXAMINE INBOX (QRESYNC (1 2 1:*))",
+            ),
+        ];
+
+        for test in tests {
+            known_answer_test_encode(test);
+        }
+    }
+
+    #[test]
+    fn test_parse_select_params() {
+        assert!(select_params(b" ()").is_err());
+        assert!(select_params(b" (UNKNOWN)").is_err());
+        assert!(select_params(b" (QRESYNC (0 1))").is_err());
+        assert!(select_params(b" (QRESYNC (1 0))").is_err());
+    }
+}
@@ -0,0 +1,281 @@
+//! Gmail IMAP Extensions (`X-GM-EXT-1`)
+
+use std::io::Write;
+
+use abnf_core::streaming::sp;
+use imap_types::{
+    command::CommandBody,
+    core::Atom,
+    extensions::gmail::GmailLabel,
+    fetch::{MessageDataItem, MessageDataItemName},
+    flag::{StoreResponse, StoreType},
+};
+use nom::{
+    branch::alt,
+    bytes::streaming::{tag, tag_no_case},
+    combinator::{map, opt, value},
+    multi::separated_list1,
+    sequence::{delimited, preceded, tuple},
+};
+
+use crate::{
+    core::{astring, atom, number64},
+    decode::IMAPResult,
+    encode::{utils::join_serializable, EncodeContext, EncodeIntoContext},
+    sequence::sequence_set,
+};
+
+/// ```abnf
+/// fetch-att =/ "X-GM-MSGID" / "X-GM-THRID" / "X-GM-LABELS"
+/// ```
+pub(crate) fn fetch_att_gmail(input: &[u8]) -> IMAPResult<&[u8], MessageDataItemName> {
+    alt((
+        value(MessageDataItemName::XGmMsgId, tag_no_case(b"X-GM-MSGID")),
+        value(MessageDataItemName::XGmThrId, tag_no_case(b"X-GM-THRID")),
+        value(MessageDataItemName::XGmLabels, tag_no_case(b"X-GM-LABELS")),
+    ))(input)
+}
+
+/// ```abnf
+/// msg-att =/ "X-GM-MSGID" SP number64 /
+///            "X-GM-THRID" SP number64 /
+///            "X-GM-LABELS" SP "(" [label *(SP label)] ")"
+/// ```
+pub(crate) fn msg_att_gmail(input: &[u8]) -> IMAPResult<&[u8], MessageDataItem> {
+    alt((
+        map(
+            preceded(tag_no_case(b"X-GM-MSGID "), number64),
+            MessageDataItem::XGmMsgId,
+        ),
+        map(
+            preceded(tag_no_case(b"X-GM-THRID "), number64),
+            MessageDataItem::XGmThrId,
+        ),
+        map(
+            preceded(tag_no_case(b"X-GM-LABELS "), label_list),
+            MessageDataItem::XGmLabels,
+        ),
+    ))(input)
+}
+
+/// ```abnf
+/// label-list = "(" [label *(SP label)] ")"
+/// ```
+fn label_list(input: &[u8]) -> IMAPResult<&[u8], Vec<GmailLabel>> {
+    map(
+        delimited(tag(b"("), opt(separated_list1(sp, label)), tag(b")")),
+        Option::unwrap_or_default,
+    )(input)
+}
+
+/// ```abnf
+/// label = "\" atom / astring
+/// ```
+///
+/// Gmail also sends system labels as strings, e.g., `"\\Inbox"`. These are normalized to
+/// [`GmailLabel::System`], so that a label has a single representation.
+fn label(input: &[u8]) -> IMAPResult<&[u8], GmailLabel> {
+    alt((
+        map(preceded(tag(b"\\"), atom), GmailLabel::System),
+        map(astring, |label| {
+            let system = match label.as_ref().strip_prefix(b"\\") {
+                Some(name) => Atom::try_from(name.to_vec()).ok(),
+                None => None,
+            };
+
+            match system {
+                Some(system) => GmailLabel::System(system),
+                None => GmailLabel::Label(label),
+            }
+        }),
+    ))(input)
+}
+
+/// ```abnf
+/// store-gmail-labels = "STORE" SP sequence-set SP store-att-labels
+///
+/// store-att-labels = (["+" / "-"] "X-GM-LABELS" [".SILENT"]) SP (label-list / (label *(SP label)))
+/// ```
+pub(crate) fn store_gmail_labels(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+    let mut parser = tuple((
+        tag_no_case(b"STORE "),
+        sequence_set,
+        sp,
+        map(
+            opt(alt((
+                value(StoreType::Add, tag(b"+")),
+                value(StoreType::Remove, tag(b"-")),
+            ))),
+            |kind| kind.unwrap_or(StoreType::Replace),
+        ),
+        tag_no_case(b"X-GM-LABELS"),
+        map(opt(tag_no_case(b".SILENT")), |silent| match silent {
+            Some(_) => StoreResponse::Silent,
+            None => StoreResponse::Answer,
+        }),
+        sp,
+        alt((label_list, separated_list1(sp, label))),
+    ));
+
+    let (remaining, (_, sequence_set, _, kind, _, response, _, labels)) = parser(input)?;
+
+    Ok((
+        remaining,
+        CommandBody::StoreGmailLabels {
+            sequence_set,
+            kind,
+            response,
+            labels,
+            uid: false,
+        },
+    ))
+}
+
+impl<'a> EncodeIntoContext for GmailLabel<'a> {
+    fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
+        match self {
+            GmailLabel::System(atom) => {
+                ctx.write_all(b"\\")?;
+                atom.encode_ctx(ctx)
+            }
+            GmailLabel::Label(astring) => astring.encode_ctx(ctx),
+        }
+    }
+}
+
+pub(crate) fn encode_label_list(
+    labels: &[GmailLabel],
+    ctx: &mut EncodeContext,
+) -> std::io::Result<()> {
+    ctx.write_all(b"(")?;
+    join_serializable(labels, b" ", ctx)?;
+    ctx.write_all(b")")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use imap_types::{
+        command::Command,
+        core::{AString, IString, Quoted, Vec1},
+        response::{Data, Response},
+    };
+
+    use super::*;
+    use crate::testing::{kat_inverse_command, kat_inverse_response};
+
+    #[test]
+    fn test_kat_inverse_response_gmail() {
+        kat_inverse_response(&[
+            (
+                b"* 1 FETCH (X-GM-LABELS (\"\\\\Inbox\" \"Work\" \"\\\\No Atom\"))\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(
+                    Data::fetch(
+                        1,
+                        vec![MessageDataItem::XGmLabels(vec![
+                            GmailLabel::System(Atom::try_from("Inbox").unwrap()),
+                            GmailLabel::Label(AString::String(IString::Quoted(
+                                Quoted::try_from("Work").unwrap(),
+                            ))),
+                            // Not an atom, thus, not a system label.
+                            GmailLabel::Label(AString::try_from("\\No Atom").unwrap()),
+                        ])],
+                    )
+                    .unwrap(),
+                ),
+            ),
+            (
+                b"* 2 FETCH (X-GM-LABELS (\\Inbox \\Sent Important \"Muy Importante\"))\r\n",
+                b"",
+                Response::Data(
+                    Data::fetch(
+                        2,
+                        vec![MessageDataItem::XGmLabels(vec![
+                            GmailLabel::System(Atom::try_from("Inbox").unwrap()),
+                            GmailLabel::System(Atom::try_from("Sent").unwrap()),
+                            GmailLabel::Label(AString::try_from("Important").unwrap()),
+                            GmailLabel::Label(AString::try_from("Muy Importante").unwrap()),
+                        ])],
+                    )
+                    .unwrap(),
+                ),
+            ),
+            (
+                b"* 3 FETCH (X-GM-MSGID 1278455344230334865 X-GM-THRID 1266894439832287888 X-GM-LABELS ())\r\n",
+                b"",
+                Response::Data(Data::Fetch {
+                    seq: NonZeroU32::new(3).unwrap(),
+                    items: Vec1::try_from(vec![
+                        MessageDataItem::XGmMsgId(1278455344230334865),
+                        MessageDataItem::XGmThrId(1266894439832287888),
+                        MessageDataItem::XGmLabels(vec![]),
+                    ])
+                    .unwrap(),
+                }),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_kat_inverse_command_gmail() {
+        kat_inverse_command(&[
+            (
+                b"A FETCH 1 (X-GM-MSGID X-GM-THRID X-GM-LABELS)\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::fetch(
+                        1,
+                        vec![
+                            MessageDataItemName::XGmMsgId,
+                            MessageDataItemName::XGmThrId,
+                            MessageDataItemName::XGmLabels,
+                        ],
+                        false,
+                    )
+                    .unwrap(),
+                )
+                .unwrap(),
+            ),
+            (
+                b"A STORE 1 +X-GM-LABELS (\\Important Work)\r\n",
+                b"",
+                Command::new(
+                    "A",
+                    CommandBody::store_gmail_labels(
+                        1,
+                        StoreType::Add,
+                        StoreResponse::Answer,
+                        vec![
+                            GmailLabel::System(Atom::try_from("Important").unwrap()),
+                            GmailLabel::Label(AString::try_from("Work").unwrap()),
+                        ],
+                        false,
+                    )
+                    .unwrap(),
+                )
+                .unwrap(),
+            ),
+            (
+                b"A UID STORE 1:* -X-GM-LABELS.SILENT (\"Muy Importante\")\r\n",
+                b"",
+                Command::new(
+                    "A",
+                    CommandBody::store_gmail_labels(
+                        "1:*",
+                        StoreType::Remove,
+                        StoreResponse::Silent,
+                        vec![GmailLabel::Label(
+                            AString::try_from("Muy Importante").unwrap(),
+                        )],
+                        true,
+                    )
+                    .unwrap(),
+                )
+                .unwrap(),
+            ),
+        ]);
+    }
+}
@@ -84,6 +84,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_id_nil_vs_empty() {
+        assert_eq!(id(b"ID NIL\r\n").unwrap().1, None);
+        assert_eq!(id(b"ID nil\r\n").unwrap().1, None);
+        assert_eq!(id(b"ID ()\r\n").unwrap().1, Some(vec![]));
+        assert!(id(b"ID\r\n").is_err());
+    }
+
     #[test]
     fn test_kat_inverse_command_id() {
         kat_inverse_command(&[
@@ -142,10 +150,25 @@ mod tests {
 
     #[test]
     fn test_kat_inverse_response_id() {
-        kat_inverse_response(&[(
-            b"* ID nil\r\n".as_ref(),
-            b"".as_ref(),
-            Response::Data(Data::Id { parameters: None }),
-        )]);
+        kat_inverse_response(&[
+            (
+                b"* ID nil\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Id { parameters: None }),
+            ),
+            (
+                b"* ID NIL\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Id { parameters: None }),
+            ),
+            #[cfg(not(feature = "quirk_id_empty_to_nil"))]
+            (
+                b"* ID ()\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Id {
+                    parameters: Some(vec![]),
+                }),
+            ),
+        ]);
     }
 }
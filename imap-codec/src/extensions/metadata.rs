@@ -530,6 +530,53 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_kat_inverse_response_metadata_unsolicited() {
+        let entry =
+            |entry: &'static str| Entry::try_from(AString::try_from(entry).unwrap()).unwrap();
+
+        kat_inverse_response(&[
+            (
+                b"* METADATA \"INBOX\" (/private/comment \"new value\" /shared/comment NIL /shared/vendor/x {3}\r\nabc)\r\n* 3 EXISTS\r\n".as_ref(),
+                b"* 3 EXISTS\r\n".as_ref(),
+                Response::Data(Data::Metadata {
+                    mailbox: Mailbox::Inbox,
+                    items: MetadataResponse::WithValues(
+                        Vec1::try_from(vec![
+                            EntryValue {
+                                entry: entry("/private/comment"),
+                                value: NString8::NString(NString::try_from("new value").unwrap()),
+                            },
+                            EntryValue {
+                                entry: entry("/shared/comment"),
+                                value: NString8::NString(NString(None)),
+                            },
+                            EntryValue {
+                                entry: entry("/shared/vendor/x"),
+                                value: NString8::NString(NString(Some(IString::Literal(
+                                    Literal::try_from("abc").unwrap(),
+                                )))),
+                            },
+                        ])
+                        .unwrap(),
+                    ),
+                }),
+            ),
+            // Unsolicited change notification without values, e.g., for the server (`""`).
+            (
+                b"* METADATA \"\" /shared/comment /shared/admin\r\n",
+                b"",
+                Response::Data(Data::Metadata {
+                    mailbox: Mailbox::try_from("").unwrap(),
+                    items: MetadataResponse::WithoutValues(
+                        Vec1::try_from(vec![entry("/shared/comment"), entry("/shared/admin")])
+                            .unwrap(),
+                    ),
+                }),
+            ),
+        ]);
+    }
+
     #[test]
     fn test_kat_inverse_response_metadata_code() {
         kat_inverse_response(&[
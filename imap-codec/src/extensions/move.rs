@@ -26,9 +26,14 @@ pub(crate) fn r#move(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
 
 #[cfg(test)]
 mod tests {
-    use imap_types::command::{Command, CommandBody};
+    use std::num::NonZeroU32;
 
-    use crate::testing::kat_inverse_command;
+    use imap_types::{
+        command::{Command, CommandBody},
+        extensions::r#move::MoveResult,
+    };
+
+    use crate::{decode::Decoder, testing::kat_inverse_command, ResponseCodec};
 
     #[test]
     fn test_kat_inverse_command_move() {
@@ -50,4 +55,66 @@ mod tests {
             ),
         ]);
     }
+
+    #[test]
+    fn test_move_result_from_responses() {
+        // RFC 6851, section 4.3 (shortened)
+        let mut input: &[u8] = b"* OK [COPYUID 432432 42:43,45 1202:1204]\r\n\
+* 22 EXPUNGE\r\n\
+* 22 EXPUNGE\r\n\
+* 24 EXPUNGE\r\n\
+* 3 EXISTS\r\n\
+a OK Move completed\r\n";
+
+        let mut responses = Vec::new();
+        while !input.is_empty() {
+            let (remaining, response) = ResponseCodec::default().decode(input).unwrap();
+            responses.push(response);
+            input = remaining;
+        }
+
+        let result = MoveResult::from_responses(&responses);
+
+        assert_eq!(
+            result.expunged,
+            [22, 22, 24].map(|seq| NonZeroU32::new(seq).unwrap())
+        );
+        #[cfg(feature = "ext_uidplus")]
+        {
+            assert_eq!(result.uid_validity, NonZeroU32::new(432432));
+            assert_eq!(
+                result
+                    .copy_uid_mapping()
+                    .map(|(from, to)| (from.get(), to.get()))
+                    .collect::<Vec<_>>(),
+                [(42, 1202), (43, 1203), (45, 1204)]
+            );
+        }
+        #[cfg(not(feature = "ext_uidplus"))]
+        {
+            assert_eq!(result.uid_validity, None);
+            assert!(result.copy_uid.is_empty());
+        }
+    }
+
+    #[cfg(feature = "ext_uidplus")]
+    #[test]
+    fn test_move_result_huge_copy_uid() {
+        let (_, response) = ResponseCodec::default()
+            .decode(b"* OK [COPYUID 1 1:4294967295 1:4294967295]\r\n")
+            .unwrap();
+
+        // Must not expand the UID sets eagerly.
+        let result = MoveResult::from_responses([&response]);
+
+        assert_eq!(result.copy_uid.len(), 1);
+        assert_eq!(
+            result
+                .copy_uid_mapping()
+                .take(2)
+                .map(|(from, to)| (from.get(), to.get()))
+                .collect::<Vec<_>>(),
+            [(1, 1), (2, 2)]
+        );
+    }
 }
@@ -0,0 +1,165 @@
+use abnf_core::streaming::sp;
+use imap_types::fetch::{MessageDataItem, MessageDataItemName};
+use nom::{
+    bytes::streaming::{tag, tag_no_case},
+    combinator::{map, opt, value},
+    multi::separated_list1,
+    sequence::{delimited, preceded, tuple},
+};
+
+use crate::{core::nstring, decode::IMAPResult};
+
+/// ```abnf
+/// fetch-att =/ "PREVIEW" [SP "(" preview-mod *(SP preview-mod) ")"]
+/// ```
+pub(crate) fn fetch_att_preview(input: &[u8]) -> IMAPResult<&[u8], MessageDataItemName> {
+    map(
+        preceded(
+            tag_no_case(b"PREVIEW"),
+            opt(preceded(
+                sp,
+                delimited(tag(b"("), separated_list1(sp, preview_mod), tag(b")")),
+            )),
+        ),
+        |modifiers| MessageDataItemName::Preview {
+            lazy: modifiers.is_some(),
+        },
+    )(input)
+}
+
+/// ```abnf
+/// preview-mod = "LAZY"
+/// ```
+fn preview_mod(input: &[u8]) -> IMAPResult<&[u8], ()> {
+    value((), tag_no_case(b"LAZY"))(input)
+}
+
+/// ```abnf
+/// msg-att-static =/ "PREVIEW" SP nstring
+/// ```
+pub(crate) fn msg_att_preview(input: &[u8]) -> IMAPResult<&[u8], MessageDataItem> {
+    map(
+        tuple((tag_no_case(b"PREVIEW"), sp, nstring)),
+        |(_, _, preview)| MessageDataItem::Preview(preview),
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use imap_types::{
+        command::{Command, CommandBody},
+        core::{NString, Vec1},
+        response::{Capability, Data, Response},
+    };
+
+    use super::*;
+    use crate::testing::{kat_inverse_command, kat_inverse_response};
+
+    #[test]
+    fn test_parse_fetch_att_preview() {
+        let tests = [
+            (
+                b"PREVIEW)".as_ref(),
+                MessageDataItemName::Preview { lazy: false },
+            ),
+            (
+                b"preview (lazy))",
+                MessageDataItemName::Preview { lazy: true },
+            ),
+        ];
+
+        for (test, expected) in tests {
+            let (rem, got) = fetch_att_preview(test).unwrap();
+            assert_eq!(rem, b")");
+            assert_eq!(got, expected);
+        }
+
+        // Unknown or missing modifiers are not consumed.
+        for test in [b"PREVIEW ())".as_ref(), b"PREVIEW (FUZZY))"] {
+            let (rem, got) = fetch_att_preview(test).unwrap();
+            assert_eq!(rem, &test[7..]);
+            assert_eq!(got, MessageDataItemName::Preview { lazy: false });
+        }
+    }
+
+    #[test]
+    fn test_kat_inverse_command_preview() {
+        kat_inverse_command(&[
+            (
+                b"A FETCH 1 (UID PREVIEW)\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::fetch(
+                        "1",
+                        vec![
+                            MessageDataItemName::Uid,
+                            MessageDataItemName::Preview { lazy: false },
+                        ],
+                        false,
+                    )
+                    .unwrap(),
+                )
+                .unwrap(),
+            ),
+            (
+                b"A FETCH 1:* PREVIEW (LAZY)\r\n?",
+                b"?",
+                Command::new(
+                    "A",
+                    CommandBody::fetch(
+                        "1:*",
+                        vec![MessageDataItemName::Preview { lazy: true }],
+                        false,
+                    )
+                    .unwrap(),
+                )
+                .unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_kat_inverse_response_preview() {
+        kat_inverse_response(&[
+            (
+                b"* 1 FETCH (PREVIEW \"Hello, this is...\")\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(
+                    Data::fetch(
+                        1,
+                        vec![MessageDataItem::Preview(
+                            NString::try_from("Hello, this is...").unwrap(),
+                        )],
+                    )
+                    .unwrap(),
+                ),
+            ),
+            (
+                b"* 2 FETCH (UID 5 PREVIEW NIL)\r\n",
+                b"",
+                Response::Data(Data::Fetch {
+                    seq: NonZeroU32::new(2).unwrap(),
+                    items: Vec1::try_from(vec![
+                        MessageDataItem::Uid(NonZeroU32::new(5).unwrap()),
+                        MessageDataItem::Preview(NString(None)),
+                    ])
+                    .unwrap(),
+                }),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_kat_inverse_response_capability_preview() {
+        kat_inverse_response(&[(
+            b"* CAPABILITY IMAP4REV1 PREVIEW\r\n".as_ref(),
+            b"".as_ref(),
+            Response::Data(
+                Data::capability(vec![Capability::Imap4Rev1, Capability::Preview]).unwrap(),
+            ),
+        )]);
+    }
+}
@@ -223,7 +223,9 @@ mod tests {
     };
 
     use super::*;
-    use crate::testing::{kat_inverse_command, kat_inverse_response};
+    use crate::testing::{
+        assert_roundtrip, kat_inverse_command, kat_inverse_response, known_answer_test_encode,
+    };
 
     #[test]
     fn test_parse_resource_name() {
@@ -249,9 +251,65 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_resource_name_negative() {
+        // A resource name must be an atom ...
+        assert!(resource_name(b"(X-FOO ").is_err());
+        assert!(resource_name(b"\"X-FOO\" ").is_err());
+        assert!(resource_name(b" X-FOO").is_err());
+
+        // ... and ends at the first non-atom character.
+        assert_eq!(
+            resource_name(b"X-FOO BAR ").unwrap(),
+            (b" BAR ".as_ref(), Resource::try_from("X-FOO").unwrap())
+        );
+        assert_eq!(
+            resource_name(b"X-FOO(BAR ").unwrap(),
+            (b"(BAR ".as_ref(), Resource::try_from("X-FOO").unwrap())
+        );
+
+        // Thus, invalid names fail to parse instead of producing a malformed `Resource::Other`.
+        assert!(setquota_resource(b"X FOO 100)").is_err());
+        assert!(setquota_resource(b"X(FOO 100)").is_err());
+        assert!(quota_resource(b"X FOO 1 100)").is_err());
+
+        for test in [
+            "", "X FOO", "X(FOO", "X)FOO", "X\"FOO", "X{FOO", "X%FOO", "X*FOO",
+        ] {
+            assert!(Resource::try_from(test).is_err(), "{test:?}");
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_quota_resource() {
+        assert_roundtrip!(QuotaGet::new(Resource::Storage, 0, 1024), quota_resource);
+        assert_roundtrip!(
+            QuotaGet::new(Resource::try_from("X-FOO").unwrap(), u64::MAX, 0),
+            quota_resource,
+        );
+        assert_roundtrip!(QuotaSet::new(Resource::Message, 512), setquota_resource);
+    }
+
+    #[test]
+    fn test_parse_quota_root_name() {
+        // `]` is an ASTRING-CHAR, but not an ATOM-CHAR.
+        let (rem, got) = quota_root_name(b"foo]bar ").unwrap();
+        assert_eq!(got, AString::try_from("foo]bar").unwrap());
+        assert!(matches!(got, AString::Atom(_)));
+        assert_eq!(rem, b" ");
+
+        let (rem, _) = atom(b"foo]bar ").unwrap();
+        assert_eq!(rem, b"]bar ");
+    }
+
     #[test]
     fn test_kat_inverse_command_get_quota() {
         kat_inverse_command(&[
+            (
+                b"A GETQUOTA foo]bar\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new("A", CommandBody::get_quota("foo]bar").unwrap()).unwrap(),
+            ),
             (
                 b"A GETQUOTA INBOX\r\n".as_ref(),
                 b"".as_ref(),
@@ -398,6 +456,48 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_encode_command_quota() {
+        let tests = [
+            (
+                CommandBody::get_quota(AString::String(IString::try_from("#user/alice").unwrap()))
+                    .unwrap(),
+                b"GETQUOTA \"#user/alice\"".as_ref(),
+            ),
+            (
+                CommandBody::get_quota_root("INBOX").unwrap(),
+                b"GETQUOTAROOT INBOX",
+            ),
+            (
+                CommandBody::set_quota(
+                    AString::String(IString::try_from("#user/alice").unwrap()),
+                    vec![
+                        QuotaSet::new(Resource::try_from("storage").unwrap(), 512),
+                        QuotaSet::new(Resource::Message, 1000),
+                    ],
+                )
+                .unwrap(),
+                b"SETQUOTA \"#user/alice\" (STORAGE 512 MESSAGE 1000)",
+            ),
+            (
+                CommandBody::set_quota(
+                    "INBOX",
+                    vec![QuotaSet::new(Resource::try_from("X-Foo").unwrap(), 0)],
+                )
+                .unwrap(),
+                b"SETQUOTA INBOX (X-Foo 0)",
+            ),
+            (
+                CommandBody::set_quota("", vec![]).unwrap(),
+                b"SETQUOTA \"\" ()",
+            ),
+        ];
+
+        for test in tests {
+            known_answer_test_encode(test);
+        }
+    }
+
     #[test]
     fn test_kat_inverse_command_status_quota() {
         kat_inverse_command(&[(
@@ -0,0 +1,133 @@
+//! IMAP REPLACE Extension
+
+use abnf_core::streaming::sp;
+use imap_types::command::CommandBody;
+#[cfg(feature = "ext_binary")]
+use imap_types::extensions::binary::LiteralOrLiteral8;
+#[cfg(feature = "ext_binary")]
+use nom::{branch::alt, combinator::map};
+use nom::{
+    bytes::streaming::tag_no_case,
+    combinator::opt,
+    sequence::{preceded, tuple},
+};
+
+#[cfg(feature = "ext_binary")]
+use crate::extensions::binary::literal8;
+use crate::{
+    core::literal, datetime::date_time, decode::IMAPResult, flag::flag_list, mailbox::mailbox,
+    sequence::seq_number,
+};
+
+/// ```abnf
+/// replace = "REPLACE" SP seq-number SP mailbox append-message
+///
+/// append-message = [SP flag-list] [SP date-time] SP literal
+/// ```
+///
+/// Note: `append-message` is simplified to match APPEND.
+pub(crate) fn replace(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+    let mut parser = tuple((
+        tag_no_case(b"REPLACE"),
+        sp,
+        seq_number,
+        sp,
+        mailbox,
+        opt(preceded(sp, flag_list)),
+        opt(preceded(sp, date_time)),
+        sp,
+        #[cfg(not(feature = "ext_binary"))]
+        literal,
+        #[cfg(feature = "ext_binary")]
+        alt((
+            map(literal, LiteralOrLiteral8::Literal),
+            map(literal8, LiteralOrLiteral8::Literal8),
+        )),
+    ));
+
+    let (remaining, (_, _, sequence, _, mailbox, flags, date, _, message)) = parser(input)?;
+
+    Ok((
+        remaining,
+        CommandBody::Replace {
+            sequence,
+            mailbox,
+            flags: flags.unwrap_or_default(),
+            date,
+            message,
+            uid: false,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use imap_types::{
+        command::{Command, CommandBody},
+        flag::Flag,
+        response::{Capability, Data, Response},
+    };
+    #[cfg(feature = "ext_uidplus")]
+    use imap_types::{
+        core::Tag,
+        response::{Code, Status},
+    };
+
+    use crate::testing::{kat_inverse_command, kat_inverse_response};
+
+    #[test]
+    fn test_kat_inverse_command_replace() {
+        kat_inverse_command(&[
+            (
+                b"A REPLACE 1 Drafts {5}\r\nHello\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::replace(1, "Drafts", vec![], None, "Hello", false).unwrap(),
+                )
+                .unwrap(),
+            ),
+            (
+                b"A UID REPLACE 42 Drafts (\\Draft) {5}\r\nHello\r\n?",
+                b"?",
+                Command::new(
+                    "A",
+                    CommandBody::replace(42, "Drafts", vec![Flag::Draft], None, "Hello", true)
+                        .unwrap(),
+                )
+                .unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_kat_inverse_response_replace() {
+        kat_inverse_response(&[(
+            b"* CAPABILITY IMAP4REV1 REPLACE\r\n".as_ref(),
+            b"".as_ref(),
+            Response::Data(
+                Data::capability(vec![Capability::Imap4Rev1, Capability::Replace]).unwrap(),
+            ),
+        )]);
+    }
+
+    #[cfg(feature = "ext_uidplus")]
+    #[test]
+    fn test_kat_inverse_response_replace_appenduid() {
+        kat_inverse_response(&[(
+            b"A OK [APPENDUID 1 2] REPLACE completed\r\n".as_ref(),
+            b"".as_ref(),
+            Response::Status(
+                Status::ok(
+                    Some(Tag::try_from("A").unwrap()),
+                    Some(Code::AppendUid {
+                        uid_validity: 1.try_into().unwrap(),
+                        uid: 2.try_into().unwrap(),
+                    }),
+                    "REPLACE completed",
+                )
+                .unwrap(),
+            ),
+        )]);
+    }
+}
@@ -0,0 +1,186 @@
+//! IMAP LIST Extension for Special-Use Mailboxes
+//!
+//! Create parameters are encoded and parsed in every build.
+
+use std::io::Write;
+
+use abnf_core::streaming::sp;
+use imap_types::{extensions::special_use::CreateParameter, flag::FlagNameAttribute};
+use nom::{
+    bytes::streaming::{tag, tag_no_case},
+    character::streaming::char,
+    combinator::map,
+    multi::{separated_list0, separated_list1},
+    sequence::{delimited, preceded},
+};
+
+use crate::{
+    core::atom,
+    decode::IMAPResult,
+    encode::{utils::join_serializable, EncodeContext, EncodeIntoContext},
+};
+
+/// ```abnf
+/// create-params = SP "(" create-param *(SP create-param) ")"
+/// ```
+///
+/// Defined in RFC 4466
+pub(crate) fn create_params(input: &[u8]) -> IMAPResult<&[u8], Vec<CreateParameter>> {
+    preceded(
+        sp,
+        delimited(tag(b"("), separated_list1(sp, create_param), tag(b")")),
+    )(input)
+}
+
+/// ```abnf
+/// create-param =/ "USE" SP "(" [use-attr *(SP use-attr)] ")"
+///
+/// use-attr = "\All" / "\Archive" / "\Drafts" / "\Flagged" /
+///            "\Junk" / "\Sent" / "\Trash" / use-attr-ext
+///
+/// use-attr-ext = "\" atom
+/// ```
+///
+/// Defined in RFC 6154
+pub(crate) fn create_param(input: &[u8]) -> IMAPResult<&[u8], CreateParameter> {
+    map(
+        preceded(
+            tag_no_case(b"USE "),
+            delimited(
+                tag(b"("),
+                separated_list0(sp, map(preceded(char('\\'), atom), FlagNameAttribute::from)),
+                tag(b")"),
+            ),
+        ),
+        CreateParameter::Use,
+    )(input)
+}
+
+impl<'a> EncodeIntoContext for CreateParameter<'a> {
+    fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
+        match self {
+            Self::Use(attributes) => {
+                ctx.write_all(b"USE (")?;
+                join_serializable(attributes, b" ", ctx)?;
+                ctx.write_all(b")")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "ext_special_use")]
+    use imap_types::response::{Code, Response, Status};
+    use imap_types::{
+        command::{Command, CommandBody},
+        core::Atom,
+        flag::FlagNameAttribute,
+        mailbox::Mailbox,
+    };
+
+    use super::*;
+    #[cfg(feature = "ext_special_use")]
+    use crate::testing::kat_inverse_response;
+    use crate::testing::{kat_inverse_command, known_answer_test_encode};
+
+    fn attribute(name: &str) -> FlagNameAttribute {
+        FlagNameAttribute::from(Atom::try_from(name).unwrap())
+    }
+
+    #[test]
+    fn test_encode_create_parameters() {
+        let tests = [
+            (
+                CommandBody::create("Archive").unwrap(),
+                b"CREATE Archive".as_ref(),
+            ),
+            (
+                CommandBody::Create {
+                    mailbox: Mailbox::try_from("Archive").unwrap(),
+                    parameters: vec![CreateParameter::Use(vec![attribute("Archive")])],
+                },
+                b"CREATE Archive (USE (\\Archive))",
+            ),
+        ];
+
+        for test in tests {
+            known_answer_test_encode(test);
+        }
+    }
+
+    #[test]
+    fn test_kat_inverse_command_create_use() {
+        kat_inverse_command(&[
+            (
+                b"A CREATE Archive (USE (\\Archive))\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::Create {
+                        mailbox: Mailbox::try_from("Archive").unwrap(),
+                        parameters: vec![CreateParameter::Use(vec![attribute("Archive")])],
+                    },
+                )
+                .unwrap(),
+            ),
+            (
+                b"A CREATE MySpecial (USE (\\Drafts \\Sent))\r\n?",
+                b"?",
+                Command::new(
+                    "A",
+                    CommandBody::Create {
+                        mailbox: Mailbox::try_from("MySpecial").unwrap(),
+                        parameters: vec![CreateParameter::Use(vec![
+                            attribute("Drafts"),
+                            attribute("Sent"),
+                        ])],
+                    },
+                )
+                .unwrap(),
+            ),
+            (
+                b"A CREATE Foo (USE ())\r\n",
+                b"",
+                Command::new(
+                    "A",
+                    CommandBody::Create {
+                        mailbox: Mailbox::try_from("Foo").unwrap(),
+                        parameters: vec![CreateParameter::Use(vec![])],
+                    },
+                )
+                .unwrap(),
+            ),
+            (
+                b"A CREATE Foo\r\n",
+                b"",
+                Command::new("A", CommandBody::create("Foo").unwrap()).unwrap(),
+            ),
+        ]);
+    }
+
+    #[cfg(feature = "ext_special_use")]
+    #[test]
+    fn test_kat_inverse_response_useattr() {
+        kat_inverse_response(&[(
+            b"A NO [USEATTR] \\All not supported\r\n".as_ref(),
+            b"".as_ref(),
+            Response::Status(
+                Status::no(
+                    Some("A".try_into().unwrap()),
+                    Some(Code::UseAttr),
+                    "\\All not supported",
+                )
+                .unwrap(),
+            ),
+        )]);
+    }
+
+    #[test]
+    fn test_parse_create_params() {
+        assert!(create_params(b" ()").is_err());
+        assert!(create_params(b" (USE)").is_err());
+        assert!(create_params(b" (USE (Archive))").is_err());
+        assert!(create_params(b" (UNKNOWN (\\Archive))").is_err());
+    }
+}
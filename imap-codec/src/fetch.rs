@@ -19,6 +19,10 @@ use nom::{
 
 #[cfg(feature = "ext_binary")]
 use crate::extensions::binary::{literal8, partial, section_binary};
+#[cfg(feature = "ext_gmail")]
+use crate::extensions::gmail::{fetch_att_gmail, msg_att_gmail};
+#[cfg(feature = "ext_preview")]
+use crate::extensions::preview::{fetch_att_preview, msg_att_preview};
 use crate::{
     body::body,
     core::{astring, nstring, number, nz_number},
@@ -39,7 +43,9 @@ use crate::{
 ///             "BODY.PEEK" section ["<" number "." nz-number ">"] /
 ///             "BINARY"      section-binary [partial] / ; RFC 3516
 ///             "BINARY.PEEK" section-binary [partial] / ; RFC 3516
-///             "BINARY.SIZE" section-binary             ; RFC 3516
+///             "BINARY.SIZE" section-binary /           ; RFC 3516
+///             "PREVIEW" [SP "(" preview-mod *(SP preview-mod) ")"] / ; RFC 8970
+///             "X-GM-MSGID" / "X-GM-THRID" / "X-GM-LABELS"          ; Gmail
 /// ```
 pub(crate) fn fetch_att(input: &[u8]) -> IMAPResult<&[u8], MessageDataItemName> {
     alt((
@@ -108,6 +114,10 @@ pub(crate) fn fetch_att(input: &[u8]) -> IMAPResult<&[u8], MessageDataItemName>
             preceded(tag_no_case("BINARY.SIZE"), section_binary),
             |section| MessageDataItemName::BinarySize { section },
         ),
+        #[cfg(feature = "ext_preview")]
+        fetch_att_preview,
+        #[cfg(feature = "ext_gmail")]
+        fetch_att_gmail,
         value(MessageDataItemName::Body, tag_no_case(b"BODY")),
         value(MessageDataItemName::Uid, tag_no_case(b"UID")),
         value(
@@ -158,7 +168,11 @@ pub(crate) fn msg_att_dynamic(input: &[u8]) -> IMAPResult<&[u8], MessageDataItem
 ///                  "BODY" section ["<" number ">"] SP nstring /
 ///                  "UID" SP uniqueid /
 ///                  "BINARY" section-binary SP (nstring / literal8) / ; RFC 3516
-///                  "BINARY.SIZE" section-binary SP number            ; RFC 3516
+///                  "BINARY.SIZE" section-binary SP number /          ; RFC 3516
+///                  "PREVIEW" SP nstring /                            ; RFC 8970
+///                  "X-GM-MSGID" SP number64 /                        ; Gmail
+///                  "X-GM-THRID" SP number64 /                        ; Gmail
+///                  "X-GM-LABELS" SP "(" [label *(SP label)] ")"      ; Gmail
 /// ```
 ///
 /// Note: MUST NOT change for a message
@@ -231,6 +245,10 @@ pub(crate) fn msg_att_static(input: &[u8]) -> IMAPResult<&[u8], MessageDataItem>
             tuple((tag_no_case(b"BINARY.SIZE"), section_binary, sp, number)),
             |(_, section, _, size)| MessageDataItem::BinarySize { section, size },
         ),
+        #[cfg(feature = "ext_preview")]
+        msg_att_preview,
+        #[cfg(feature = "ext_gmail")]
+        msg_att_gmail,
     ))(input)
 }
 
@@ -336,13 +354,15 @@ pub(crate) fn header_fld_name(input: &[u8]) -> IMAPResult<&[u8], AString> {
 mod tests {
     use imap_types::{
         body::{BasicFields, Body, BodyStructure, SpecificFields},
-        core::{IString, NString},
+        core::{IString, Literal, NString},
         datetime::DateTime,
         envelope::Envelope,
+        flag::{Flag, FlagFetch},
+        response::{Data, Response},
     };
 
     use super::*;
-    use crate::testing::known_answer_test_encode;
+    use crate::testing::{kat_inverse_response, known_answer_test_encode};
 
     #[test]
     fn test_encode_message_data_item_name() {
@@ -525,4 +545,148 @@ mod tests {
             known_answer_test_encode(test)
         }
     }
+
+    #[test]
+    fn test_kat_inverse_response_fetch_internal_date() {
+        kat_inverse_response(&[(
+            b"* 12 FETCH (FLAGS (\\Seen \\Answered) INTERNALDATE \"17-Jul-1996 02:44:25 -0700\" RFC822.SIZE 4286)\r\n".as_ref(),
+            b"".as_ref(),
+            Response::Data(
+                Data::fetch(
+                    12,
+                    vec![
+                        MessageDataItem::Flags(vec![
+                            FlagFetch::Flag(Flag::Seen),
+                            FlagFetch::Flag(Flag::Answered),
+                        ]),
+                        MessageDataItem::InternalDate(
+                            DateTime::try_from(
+                                chrono::DateTime::parse_from_rfc3339("1996-07-17T02:44:25-07:00")
+                                    .unwrap(),
+                            )
+                            .unwrap(),
+                        ),
+                        MessageDataItem::Rfc822Size(4286),
+                    ],
+                )
+                .unwrap(),
+            ),
+        )]);
+    }
+
+    #[test]
+    fn test_parse_uid_fetch_response_seq_number_and_uid() {
+        // Response to, e.g., `A1 UID FETCH 4827313 FLAGS`.
+        let (rem, got) =
+            crate::response::response(b"* 23 FETCH (FLAGS (\\Seen) UID 4827313)\r\n").unwrap();
+        assert!(rem.is_empty());
+
+        let data = match got {
+            Response::Data(data) => data,
+            _ => unreachable!(),
+        };
+        assert_eq!(data.seq_number(), NonZeroU32::new(23));
+        assert_eq!(data.uid(), NonZeroU32::new(4827313));
+
+        let (_, got) = crate::response::response(b"* 23 FETCH (FLAGS (\\Seen))\r\n").unwrap();
+        assert!(matches!(
+            got,
+            Response::Data(data) if data.seq_number() == NonZeroU32::new(23) && data.uid().is_none()
+        ));
+        assert_eq!(Data::Exists(1).seq_number(), None);
+    }
+
+    #[test]
+    fn test_kat_inverse_response_fetch_mixed_attributes() {
+        kat_inverse_response(&[(
+            b"* 1 FETCH (UID 5 FLAGS (\\Seen) INTERNALDATE \"17-Jul-1996 02:44:25 -0700\" RFC822.SIZE 5 ENVELOPE (NIL \"Hi\" NIL NIL NIL NIL NIL NIL NIL NIL) BODY[] {5}\r\nHello BODY[HEADER] NIL FLAGS ())\r\n".as_ref(),
+            b"".as_ref(),
+            Response::Data(
+                Data::fetch(
+                    1,
+                    vec![
+                        MessageDataItem::Uid(NonZeroU32::new(5).unwrap()),
+                        MessageDataItem::Flags(vec![FlagFetch::Flag(Flag::Seen)]),
+                        MessageDataItem::InternalDate(
+                            DateTime::try_from(
+                                chrono::DateTime::parse_from_rfc3339("1996-07-17T02:44:25-07:00")
+                                    .unwrap(),
+                            )
+                            .unwrap(),
+                        ),
+                        MessageDataItem::Rfc822Size(5),
+                        MessageDataItem::Envelope(Envelope {
+                            date: NString(None),
+                            subject: NString::try_from("Hi").unwrap(),
+                            from: vec![],
+                            sender: vec![],
+                            reply_to: vec![],
+                            to: vec![],
+                            cc: vec![],
+                            bcc: vec![],
+                            in_reply_to: NString(None),
+                            message_id: NString(None),
+                        }),
+                        MessageDataItem::BodyExt {
+                            section: None,
+                            origin: None,
+                            data: NString::from(Literal::try_from("Hello").unwrap()),
+                        },
+                        MessageDataItem::BodyExt {
+                            section: Some(Section::Header(None)),
+                            origin: None,
+                            data: NString(None),
+                        },
+                        MessageDataItem::Flags(vec![]),
+                    ],
+                )
+                .unwrap(),
+            ),
+        )]);
+    }
+
+    #[test]
+    fn test_kat_inverse_response_fetch_body_nil() {
+        kat_inverse_response(&[
+            (
+                b"* 1 FETCH (BODY[1.5] NIL)\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(
+                    Data::fetch(
+                        1,
+                        vec![MessageDataItem::BodyExt {
+                            section: Some(Section::Part(Part(
+                                Vec1::try_from(vec![
+                                    NonZeroU32::new(1).unwrap(),
+                                    NonZeroU32::new(5).unwrap(),
+                                ])
+                                .unwrap(),
+                            ))),
+                            origin: None,
+                            data: NString(None),
+                        }],
+                    )
+                    .unwrap(),
+                ),
+            ),
+            (
+                b"* 2 FETCH (UID 7 BODY[] NIL)\r\n",
+                b"",
+                Response::Data(
+                    Data::fetch(
+                        2,
+                        vec![
+                            MessageDataItem::Uid(NonZeroU32::new(7).unwrap()),
+                            MessageDataItem::BodyExt {
+                                section: None,
+                                origin: None,
+                                data: NString(None),
+                            },
+                        ],
+                    )
+                    .unwrap(),
+                ),
+            ),
+        ]);
+    }
 }
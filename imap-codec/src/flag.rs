@@ -181,6 +181,15 @@ mod tests {
                 vec![FlagNameAttribute::from(Atom::try_from("Markedm").unwrap())],
             ),
             ("\\Marked)", vec![FlagNameAttribute::Marked]),
+            (
+                "\\Noselect \\noinferiors \\MARKED \\Unmarked)",
+                vec![
+                    FlagNameAttribute::Noselect,
+                    FlagNameAttribute::Noinferiors,
+                    FlagNameAttribute::Marked,
+                    FlagNameAttribute::Unmarked,
+                ],
+            ),
         ];
 
         for (test, expected) in tests {
@@ -8,6 +8,9 @@
 //! [`CommandCodec`] (to parse commands from a client), and
 //! [`ResponseCodec`] (to parse responses or results from a server).
 //!
+//! The [`client`](crate::client) and [`server`](crate::server) modules provide functions that only
+//! decode and encode the messages relevant to one side of a connection.
+//!
 //! Note that IMAP traces are not guaranteed to be UTF-8.
 //! Thus, be careful when using code like `from_utf8(...)`.
 //!
@@ -18,6 +21,13 @@
 //!
 //! **Note:** Decoding IMAP traces is more elaborate than it seems on a first glance.
 //! Please consult the [`decode`](`crate::decode`) module documentation to learn how to handle real-world decoding.
+//! Large literals can be streamed without buffering them using the [`stream`](`crate::stream`) module.
+//! Commands containing literals can be sent step by step using the [`transmit`](`crate::transmit`) module.
+//! Responses can be matched with the command they complete using the [`collect`](`crate::collect`) module.
+//! Extension authors can reuse parsers for core grammar elements from the [`parse`](`crate::parse`) module.
+//!
+//! **Note:** The [`parse`](`crate::parse`) module exposes [nom] 7 types. Thus, [`nom`](crate::nom) is re-exported,
+//! and upgrading it to a new major version is a breaking change of imap-codec.
 //!
 //! ### Example
 //!
@@ -80,6 +90,8 @@
 //!
 //! | Feature               | Description                    | Enabled by default |
 //! |-----------------------|--------------------------------|--------------------|
+//! | bytes                 | Encode into `bytes::BytesMut`. | No                 |
+//! | decode_stats          | Collect decoding statistics.   | No                 |
 //! | quirk_crlf_relaxed    | Make `\r` in `\r\n` optional.  | No                 |
 //! | quirk_rectify_numbers | Rectify (invalid) numbers.     | No                 |
 //! | quirk_missing_text    | Rectify missing `text` element.| No                 |
@@ -133,3 +145,5 @@ mod testing;
 pub use codec::*;
 // Re-export.
 pub use imap_types;
+// Re-export (used in the public API of `parse`).
+pub use nom;
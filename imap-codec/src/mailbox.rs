@@ -1,3 +1,5 @@
+use std::num::NonZeroU64;
+
 use abnf_core::streaming::{dquote, sp};
 use imap_types::{
     core::QuotedChar,
@@ -10,7 +12,7 @@ use nom::{
     branch::alt,
     bytes::streaming::{tag, tag_no_case, take_while1},
     combinator::{map, opt, value},
-    multi::many0,
+    multi::{many0, many1},
     sequence::{delimited, preceded, tuple},
 };
 
@@ -19,7 +21,7 @@ use crate::extensions::metadata::metadata_resp;
 #[cfg(feature = "ext_sort_thread")]
 use crate::extensions::thread::thread_data;
 use crate::{
-    core::{astring, nil, number, nz_number, quoted_char, string},
+    core::{astring, mod_sequence_value, nil, number, nz_number, quoted_char, string},
     decode::IMAPResult,
     extensions::quota::{quota_response, quotaroot_response},
     flag::{flag_list, mbx_list_flags},
@@ -61,6 +63,7 @@ pub(crate) fn mailbox(input: &[u8]) -> IMAPResult<&[u8], Mailbox> {
 ///                "LIST" SP mailbox-list /
 ///                "LSUB" SP mailbox-list /
 ///                "SEARCH" *(SP nz-number) /
+///                "SEARCH" [1*(SP nz-number) SP search-sort-mod-seq] / ; RFC 7162
 ///                "STATUS" SP mailbox SP "(" [status-att-list] ")" /
 ///                "METADATA" SP mailbox SP (entry-values / entry-list) / ; RFC 5464
 ///                number SP "EXISTS" /
@@ -89,8 +92,17 @@ pub(crate) fn mailbox_data(input: &[u8]) -> IMAPResult<&[u8], Data> {
             },
         ),
         map(
-            tuple((tag_no_case(b"SEARCH"), many0(preceded(sp, nz_number)))),
-            |(_, nums)| Data::Search(nums),
+            preceded(
+                tag_no_case(b"SEARCH"),
+                alt((
+                    map(
+                        tuple((many1(preceded(sp, nz_number)), sp, search_sort_mod_seq)),
+                        |(numbers, _, modseq)| (numbers, Some(modseq)),
+                    ),
+                    map(many0(preceded(sp, nz_number)), |numbers| (numbers, None)),
+                )),
+            ),
+            |(numbers, modseq)| Data::Search { numbers, modseq },
         ),
         #[cfg(feature = "ext_sort_thread")]
         map(
@@ -154,9 +166,27 @@ pub(crate) fn mailbox_list(
     Ok((remaining, (mbx_list_flags, maybe_delimiter, mailbox)))
 }
 
+/// ```abnf
+/// search-sort-mod-seq = "(" "MODSEQ" SP mod-sequence-value ")"
+/// ```
+///
+/// Defined in RFC 7162
+pub(crate) fn search_sort_mod_seq(input: &[u8]) -> IMAPResult<&[u8], NonZeroU64> {
+    delimited(
+        tag(b"("),
+        preceded(tag_no_case(b"MODSEQ "), mod_sequence_value),
+        tag(b")"),
+    )(input)
+}
+
 #[cfg(test)]
 mod tests {
+    use std::num::NonZeroU32;
+
+    use imap_types::{core::Atom, flag::Flag, response::Response};
+
     use super::*;
+    use crate::{decode::Decoder, encode::Encoder, testing::kat_inverse_response, ResponseCodec};
 
     #[test]
     fn test_mailbox() {
@@ -166,4 +196,135 @@ mod tests {
         assert!(mailbox(b"inbox.sent ").is_ok());
         assert!(mailbox(b"aaa").is_err());
     }
+
+    #[test]
+    fn test_parse_mailbox_data_list_noselect() {
+        let (rem, got) =
+            mailbox_data(b"LIST (\\Noselect \\HasChildren) \".\" Archive\r\n").unwrap();
+        assert_eq!(rem, b"\r\n");
+        assert_eq!(
+            got,
+            Data::List {
+                items: vec![
+                    FlagNameAttribute::Noselect,
+                    FlagNameAttribute::from(Atom::try_from("HasChildren").unwrap()),
+                ],
+                delimiter: Some(QuotedChar::try_from('.').unwrap()),
+                mailbox: Mailbox::try_from("Archive").unwrap(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_mailbox_data_flags() {
+        let (rem, got) =
+            mailbox_data(b"FLAGS (\\Answered \\Flagged \\Deleted \\Seen \\Draft)\r\n").unwrap();
+        assert_eq!(rem, b"\r\n");
+        assert_eq!(
+            got,
+            Data::Flags(vec![
+                Flag::Answered,
+                Flag::Flagged,
+                Flag::Deleted,
+                Flag::Seen,
+                Flag::Draft,
+            ])
+        );
+
+        // `PERMANENTFLAGS` is a response code, not mailbox data.
+        assert!(mailbox_data(b"PERMANENTFLAGS (\\Seen \\*)\r\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_mailbox_data_search() {
+        let tests = [
+            (b"SEARCH\r\n".as_ref(), vec![]),
+            (b"SEARCH 2 5 6\r\n", vec![2, 5, 6]),
+        ];
+
+        for (test, expected) in tests {
+            let (rem, got) = mailbox_data(test).unwrap();
+            assert_eq!(rem, b"\r\n");
+            assert_eq!(
+                got,
+                Data::search(
+                    expected
+                        .into_iter()
+                        .map(|n| NonZeroU32::new(n).unwrap())
+                        .collect()
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_kat_inverse_response_search_modseq() {
+        kat_inverse_response(&[
+            (
+                b"* SEARCH 2 5 6 (MODSEQ 917162500)\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Search {
+                    numbers: [2, 5, 6].map(|n| NonZeroU32::new(n).unwrap()).to_vec(),
+                    modseq: NonZeroU64::new(917162500),
+                }),
+            ),
+            (
+                b"* SEARCH 2 5 6\r\n",
+                b"",
+                Response::Data(Data::Search {
+                    numbers: [2, 5, 6].map(|n| NonZeroU32::new(n).unwrap()).to_vec(),
+                    modseq: None,
+                }),
+            ),
+        ]);
+
+        // `MODSEQ` must be positive.
+        let (rem, _) = mailbox_data(b"SEARCH 2 (MODSEQ 0)\r\n").unwrap();
+        assert_eq!(rem, b" (MODSEQ 0)\r\n");
+
+        // `MODSEQ` requires at least one number.
+        assert!(ResponseCodec::default()
+            .decode(b"* SEARCH (MODSEQ 1)\r\n")
+            .is_err());
+    }
+
+    #[test]
+    fn test_kat_inverse_response_search_condstore_empty() {
+        // A CONDSTORE search without matches has no highest mod-sequence to report (RFC 7162,
+        // 3.1.5), and `search-sort-mod-seq` requires at least one number anyway.
+        kat_inverse_response(&[
+            (
+                b"* SEARCH\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Search {
+                    numbers: vec![],
+                    modseq: None,
+                }),
+            ),
+            (
+                b"* SEARCH\r\nA OK Search completed\r\n",
+                b"A OK Search completed\r\n",
+                Response::Data(Data::Search {
+                    numbers: vec![],
+                    modseq: None,
+                }),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_encode_search_modseq_without_numbers() {
+        // `MODSEQ` requires at least one number and is dropped otherwise.
+        let data = Data::Search {
+            numbers: vec![],
+            modseq: NonZeroU64::new(1),
+        };
+
+        assert_eq!(
+            ResponseCodec::default()
+                .encode(&Response::Data(data))
+                .dump(),
+            b"* SEARCH\r\n"
+        );
+    }
 }
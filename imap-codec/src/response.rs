@@ -67,15 +67,36 @@ pub(crate) fn resp_cond_auth(
             value(GreetingKind::Ok, tag_no_case(b"OK")),
             value(GreetingKind::PreAuth, tag_no_case(b"PREAUTH")),
         )),
-        sp,
-        resp_text,
+        sp_resp_text,
     ));
 
-    let (remaining, (kind, _, resp_text)) = parser(input)?;
+    let (remaining, (kind, resp_text)) = parser(input)?;
 
     Ok((remaining, (kind, resp_text)))
 }
 
+/// `SP resp-text`
+///
+/// With `quirk_missing_text`, a status without any text, e.g., `A1 OK\r\n`, is accepted, too.
+fn sp_resp_text(input: &[u8]) -> IMAPResult<&[u8], (Option<Code>, Text)> {
+    #[cfg(not(feature = "quirk_missing_text"))]
+    {
+        preceded(sp, resp_text)(input)
+    }
+
+    #[cfg(feature = "quirk_missing_text")]
+    {
+        alt((
+            preceded(sp, resp_text),
+            map(peek(crlf), |_| {
+                log::warn!("Rectified missing `text` to \"...\"");
+
+                (None, Text::unvalidated("..."))
+            }),
+        ))(input)
+    }
+}
+
 /// `resp-text = ["[" resp-text-code "]" SP] text`
 pub(crate) fn resp_text(input: &[u8]) -> IMAPResult<&[u8], (Option<Code>, Text)> {
     // When the text starts with "[", we insist to parse a code.
@@ -139,10 +160,15 @@ pub(crate) fn resp_text(input: &[u8]) -> IMAPResult<&[u8], (Option<Code>, Text)>
 ///                    "NOPRIVATE"
 ///                  ) /
 ///                  "UNKNOWN-CTE" /       ; RFC 3516
+///                  "USEATTR" /           ; RFC 6154
 ///                  atom [SP 1*<any TEXT-CHAR except "]">]
 /// ```
 ///
 /// Note: See errata id: 261
+///
+/// Note: Codes with invalid arguments, e.g., `UIDVALIDITY 0`, are not parsed by this function but
+/// end up as [`Code::Other`] (see [`resp_text`]). Thus, an invalid value is never mistaken for a
+/// valid one, and the response is still usable.
 pub(crate) fn resp_text_code(input: &[u8]) -> IMAPResult<&[u8], Code> {
     alt((
         value(Code::Alert, tag_no_case(b"ALERT")),
@@ -205,6 +231,8 @@ pub(crate) fn resp_text_code(input: &[u8]) -> IMAPResult<&[u8], Code> {
         resp_code_copy,
         #[cfg(feature = "ext_uidplus")]
         value(Code::UidNotSticky, tag_no_case(b"UIDNOTSTICKY")),
+        #[cfg(feature = "ext_special_use")]
+        value(Code::UseAttr, tag_no_case(b"USEATTR")),
     ))(input)
 }
 
@@ -233,9 +261,9 @@ pub(crate) fn capability(input: &[u8]) -> IMAPResult<&[u8], Capability> {
 
 /// `resp-cond-bye = "BYE" SP resp-text`
 pub(crate) fn resp_cond_bye(input: &[u8]) -> IMAPResult<&[u8], (Option<Code>, Text)> {
-    let mut parser = tuple((tag_no_case(b"BYE"), sp, resp_text));
+    let mut parser = tuple((tag_no_case(b"BYE"), sp_resp_text));
 
-    let (remaining, (_, _, resp_text)) = parser(input)?;
+    let (remaining, (_, resp_text)) = parser(input)?;
 
     Ok((remaining, resp_text))
 }
@@ -257,6 +285,10 @@ pub(crate) fn response(input: &[u8]) -> IMAPResult<&[u8], Response> {
 }
 
 /// `continue-req = "+" SP (resp-text / base64) CRLF`
+///
+/// Note: An empty continuation request, i.e., `+ \r\n`, is parsed as empty `base64`. It can't be
+/// `resp-text` because `text` must not be empty. (An empty SASL challenge looks the same.)
+/// With `quirk_missing_text`, a bare `+\r\n` is accepted, too.
 pub(crate) fn continue_req(input: &[u8]) -> IMAPResult<&[u8], CommandContinuationRequest> {
     // We can't map the output of `resp_text` directly to `Continue::basic()` because we might end
     // up with a subset of `Text` that is valid base64 and will panic on `unwrap()`. Thus, we first
@@ -269,7 +301,18 @@ pub(crate) fn continue_req(input: &[u8]) -> IMAPResult<&[u8], CommandContinuatio
     }
 
     let mut parser = tuple((
-        tag(b"+ "),
+        tag(b"+"),
+        #[cfg(not(feature = "quirk_missing_text"))]
+        sp,
+        #[cfg(feature = "quirk_missing_text")]
+        alt((
+            sp,
+            map(peek(crlf), |_| {
+                log::warn!("Rectified missing SP in continuation request");
+
+                b"".as_ref()
+            }),
+        )),
         alt((
             #[cfg(not(feature = "quirk_crlf_relaxed"))]
             map(
@@ -292,7 +335,7 @@ pub(crate) fn continue_req(input: &[u8]) -> IMAPResult<&[u8], CommandContinuatio
         crlf,
     ));
 
-    let (remaining, (_, either, _)) = parser(input)?;
+    let (remaining, (_, _, either, _)) = parser(input)?;
 
     let continue_request = match either {
         Either::Base64(data) => CommandContinuationRequest::base64(data),
@@ -352,11 +395,10 @@ pub(crate) fn resp_cond_state(input: &[u8]) -> IMAPResult<&[u8], (StatusKind, Op
             value(StatusKind::No, tag_no_case("NO")),
             value(StatusKind::Bad, tag_no_case("BAD")),
         )),
-        sp,
-        resp_text,
+        sp_resp_text,
     ));
 
-    let (remaining, (kind, _, (maybe_code, text))) = parser(input)?;
+    let (remaining, (kind, (maybe_code, text))) = parser(input)?;
 
     Ok((remaining, (kind, maybe_code, text)))
 }
@@ -410,16 +452,27 @@ mod tests {
     use std::num::NonZeroU32;
 
     use imap_types::{
+        auth::AuthMechanism,
         body::{
             BasicFields, Body, BodyExtension, BodyStructure, Disposition, Language, Location,
             SinglePartExtensionData, SpecificFields,
         },
         core::{IString, NString, QuotedChar, Tag},
-        flag::FlagNameAttribute,
+        fetch::MessageDataItem,
+        flag::{Flag, FlagFetch, FlagNameAttribute, FlagPerm},
+        mailbox::Mailbox,
+        response::{Capabilities, CodeRegistry, GreetingBuilder, StatusBuilder},
+        state::MailboxState,
+        status::StatusDataItem,
     };
 
     use super::*;
-    use crate::testing::{kat_inverse_greeting, kat_inverse_response, known_answer_test_encode};
+    use crate::{
+        decode::{Decoder, ResponseDecodeError},
+        encode::Encoder,
+        testing::{kat_inverse_greeting, kat_inverse_response, known_answer_test_encode},
+        ResponseCodec,
+    };
 
     #[test]
     fn test_kat_inverse_greeting() {
@@ -472,10 +525,29 @@ mod tests {
                     mailbox: "bbb".try_into().unwrap(),
                 }),
             ),
+            (
+                b"* LIST (\\Noselect) \"/\" \"\"\r\n",
+                b"",
+                Response::Data(Data::List {
+                    items: vec![FlagNameAttribute::Noselect],
+                    delimiter: Some(QuotedChar::try_from('/').unwrap()),
+                    mailbox: "".try_into().unwrap(),
+                }),
+            ),
+            (
+                b"* LSUB () \"/\" INBOX\r\n",
+                b"",
+                Response::Data(Data::Lsub {
+                    items: vec![],
+                    delimiter: Some(QuotedChar::try_from('/').unwrap()),
+                    mailbox: Mailbox::Inbox,
+                }),
+            ),
+            (b"* SEARCH\r\n", b"", Response::Data(Data::search(vec![]))),
             (
                 b"* SEARCH 1 2 3 42\r\n",
                 b"",
-                Response::Data(Data::Search(vec![
+                Response::Data(Data::search(vec![
                     1.try_into().unwrap(),
                     2.try_into().unwrap(),
                     3.try_into().unwrap(),
@@ -496,6 +568,45 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_parse_unsolicited_flag_update() {
+        let (rem, got) = ResponseCodec::default()
+            .decode(b"* 12 FETCH (FLAGS (\\Seen \\Deleted))\r\n")
+            .unwrap();
+        assert!(rem.is_empty());
+
+        let data = match got {
+            Response::Data(data) => data,
+            _ => panic!("expected data"),
+        };
+        assert_eq!(
+            data.flag_update(),
+            Some((
+                NonZeroU32::new(12).unwrap(),
+                [FlagFetch::Flag(Flag::Seen), FlagFetch::Flag(Flag::Deleted)].as_slice()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_capability_vendor_extensions() {
+        let (rem, got) = ResponseCodec::default()
+            .decode(b"* CAPABILITY IMAP4rev1 X-GM-EXT-1 IDLE x-Draft-I2-NOTIFY UNKNOWN\r\n")
+            .unwrap();
+        assert!(rem.is_empty());
+
+        let data = match got {
+            Response::Data(data) => data,
+            _ => panic!("expected data"),
+        };
+        assert_eq!(
+            data.vendor_extensions()
+                .map(AsRef::as_ref)
+                .collect::<Vec<&str>>(),
+            ["X-GM-EXT-1", "x-Draft-I2-NOTIFY"]
+        );
+    }
+
     #[test]
     fn test_kat_inverse_response_status() {
         kat_inverse_response(&[
@@ -597,6 +708,201 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_parse_unsolicited_capability() {
+        let mut capabilities = Capabilities::new([Capability::Imap4Rev1, Capability::LiteralPlus]);
+
+        // E.g., after authentication, in the middle of a session.
+        let (rem, got) = response(b"* CAPABILITY IMAP4rev1 IDLE MOVE\r\n* 1 EXISTS\r\n").unwrap();
+        assert_eq!(rem, b"* 1 EXISTS\r\n");
+        assert_eq!(
+            got,
+            Response::Data(Data::Capability(
+                Vec1::try_from(vec![
+                    Capability::Imap4Rev1,
+                    Capability::Idle,
+                    Capability::Move
+                ])
+                .unwrap()
+            ))
+        );
+
+        assert!(capabilities.update(&got));
+        assert_eq!(
+            capabilities.as_ref(),
+            [
+                Capability::Imap4Rev1,
+                Capability::LiteralPlus,
+                Capability::Idle,
+                Capability::Move
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fold_select_responses() {
+        let mut input: &[u8] = b"* 172 EXISTS\r\n\
+* 1 RECENT\r\n\
+* OK [UNSEEN 12] Message 12 is first unseen\r\n\
+* OK [UIDVALIDITY 3857529045] UIDs valid\r\n\
+* OK [UIDNEXT 4392] Predicted next UID\r\n\
+* FLAGS (\\Answered \\Flagged \\Deleted \\Seen \\Draft)\r\n\
+* OK [PERMANENTFLAGS (\\Deleted \\Seen \\*)] Limited\r\n\
+A142 OK [READ-WRITE] SELECT completed\r\n";
+
+        let mut state = MailboxState::default();
+
+        while !input.is_empty() {
+            let (rem, got) = response(input).unwrap();
+            assert!(state.update(&got));
+            input = rem;
+        }
+
+        assert_eq!(state.exists(), Some(172));
+        assert_eq!(state.recent(), Some(1));
+        assert_eq!(state.first_unseen(), NonZeroU32::new(12));
+        assert_eq!(state.uid_validity(), NonZeroU32::new(3857529045));
+        assert_eq!(state.uid_next(), NonZeroU32::new(4392));
+        assert_eq!(state.flags().len(), 5);
+        assert_eq!(
+            state.permanent_flags().unwrap(),
+            [
+                FlagPerm::Flag(Flag::Deleted),
+                FlagPerm::Flag(Flag::Seen),
+                FlagPerm::Asterisk
+            ]
+        );
+        assert_eq!(state.read_only(), Some(false));
+    }
+
+    #[test]
+    fn test_fold_select_responses_expunge() {
+        let mut input: &[u8] = b"* 3 EXISTS\r\n\
+* 0 RECENT\r\n\
+* OK [UIDVALIDITY 3857529045] UIDs valid\r\n\
+A142 OK [READ-WRITE] SELECT completed\r\n\
+* 3 EXPUNGE\r\n";
+
+        let mut state = MailboxState::default();
+
+        while !input.is_empty() {
+            let (rem, got) = response(input).unwrap();
+            assert!(state.update(&got));
+            input = rem;
+        }
+
+        assert_eq!(state.exists(), Some(2));
+
+        for _ in 0..3 {
+            state.update(&Response::Data(Data::Expunge(NonZeroU32::new(1).unwrap())));
+        }
+
+        assert_eq!(state.exists(), Some(0));
+    }
+
+    #[test]
+    fn test_parse_tagged_ok_with_capabilities() {
+        let (rem, got) =
+            response(b"A1 OK [CAPABILITY IMAP4rev1 IDLE AUTH=PLAIN] Logged in\r\n").unwrap();
+        assert!(rem.is_empty());
+
+        let status = match got {
+            Response::Status(status) => status,
+            _ => unreachable!(),
+        };
+        assert!(status.is_tagged());
+        assert_eq!(
+            status.capabilities(),
+            Some(
+                [
+                    Capability::Imap4Rev1,
+                    Capability::Idle,
+                    Capability::Auth(AuthMechanism::Plain)
+                ]
+                .as_ref()
+            )
+        );
+
+        let (_, got) = response(b"A1 OK Logged in\r\n").unwrap();
+        assert!(matches!(
+            got,
+            Response::Status(status) if status.capabilities().is_none()
+        ));
+    }
+
+    #[test]
+    fn test_parse_alert_text() {
+        let (rem, got) = response(b"* OK [ALERT] Your quota is nearly full.\r\n").unwrap();
+        assert!(rem.is_empty());
+
+        let status = match got {
+            Response::Status(status) => status,
+            _ => unreachable!(),
+        };
+        assert_eq!(status.alert_text(), Some("Your quota is nearly full."));
+
+        let (_, got) = response(b"A1 NO [ALERT] Mailbox is full\r\n").unwrap();
+        assert!(matches!(
+            got,
+            Response::Status(status) if status.alert_text() == Some("Mailbox is full")
+        ));
+
+        let (_, got) = response(b"* OK [UNSEEN 1] Your quota is nearly full.\r\n").unwrap();
+        assert!(matches!(
+            got,
+            Response::Status(status) if status.alert_text().is_none()
+        ));
+    }
+
+    #[test]
+    fn test_parse_untagged_no_bad() {
+        let tests = [
+            (
+                b"* NO [OVERQUOTA] Mailbox almost full\r\n".as_ref(),
+                Status::no(None, Some(Code::OverQuota), "Mailbox almost full").unwrap(),
+            ),
+            (
+                b"* BAD Disk error\r\n",
+                Status::bad(None, None, "Disk error").unwrap(),
+            ),
+        ];
+
+        for (test, expected) in tests {
+            let (rem, got) = response(test).unwrap();
+            assert!(rem.is_empty());
+            assert_eq!(got, Response::Status(expected.clone()));
+
+            // Untagged NO/BAD are informational and don't complete a command.
+            assert!(expected.is_untagged());
+            assert_eq!(expected.tag(), None);
+        }
+    }
+
+    #[test]
+    fn test_parse_continue_req_empty() {
+        let empty = CommandContinuationRequest::base64(b"".as_ref());
+
+        assert_eq!(
+            continue_req(b"+ \r\n").unwrap(),
+            (b"".as_ref(), empty.clone())
+        );
+        assert_eq!(
+            response(b"+ \r\n?").unwrap(),
+            (
+                b"?".as_ref(),
+                Response::CommandContinuationRequest(empty.clone())
+            )
+        );
+
+        #[cfg(not(feature = "quirk_missing_text"))]
+        assert!(continue_req(b"+\r\n").is_err());
+        #[cfg(feature = "quirk_missing_text")]
+        assert_eq!(continue_req(b"+\r\n").unwrap(), (b"".as_ref(), empty));
+
+        assert!(matches!(continue_req(b"+"), Err(nom::Err::Incomplete(_))));
+        assert!(matches!(continue_req(b"+ "), Err(nom::Err::Incomplete(_))));
+    }
+
     /*
     // TODO(#184)
     #[test]
@@ -621,6 +927,133 @@ mod tests {
     }
     */
 
+    #[test]
+    fn test_kat_inverse_greeting_builder() {
+        kat_inverse_greeting(&[
+            (
+                b"* OK [CAPABILITY IMAP4REV1 AUTH=PLAIN IDLE] Ready.\r\n".as_ref(),
+                b"".as_ref(),
+                GreetingBuilder::ok()
+                    .capabilities(
+                        Vec1::try_from(vec![
+                            Capability::Imap4Rev1,
+                            Capability::Auth(AuthMechanism::Plain),
+                            Capability::Idle,
+                        ])
+                        .unwrap(),
+                    )
+                    .text("Ready.")
+                    .unwrap(),
+            ),
+            (
+                b"* PREAUTH [ALERT] Logged in as alice\r\n",
+                b"",
+                GreetingBuilder::preauth()
+                    .code(Code::Alert)
+                    .text("Logged in as alice")
+                    .unwrap(),
+            ),
+            (
+                b"* BYE Too many connections\r\n",
+                b"",
+                GreetingBuilder::bye().text("Too many connections").unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_encode_status_builder() {
+        let tag = || Some(Tag::try_from("A1").unwrap());
+
+        let tests = [
+            (
+                StatusBuilder::ok(tag())
+                    .code(Code::ReadWrite)
+                    .text("Select completed.")
+                    .unwrap(),
+                b"A1 OK [READ-WRITE] Select completed.\r\n".as_ref(),
+            ),
+            (
+                StatusBuilder::no(tag()).text("No such mailbox").unwrap(),
+                b"A1 NO No such mailbox\r\n",
+            ),
+            (
+                StatusBuilder::bad(None).text("Command unknown").unwrap(),
+                b"* BAD Command unknown\r\n",
+            ),
+            (
+                StatusBuilder::ok(None)
+                    .code(Code::UidNext(NonZeroU32::new(4392).unwrap()))
+                    .text("Predicted next UID")
+                    .unwrap(),
+                b"* OK [UIDNEXT 4392] Predicted next UID\r\n",
+            ),
+            (
+                StatusBuilder::bye()
+                    .code(Code::Alert)
+                    .text("Server shutting down")
+                    .unwrap(),
+                b"* BYE [ALERT] Server shutting down\r\n",
+            ),
+        ];
+
+        for (status, expected) in tests {
+            assert_eq!(
+                ResponseCodec::default()
+                    .encode(&Response::Status(status))
+                    .dump(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_data_constructors() {
+        let tests = [
+            Data::exists(172),
+            Data::recent(1),
+            Data::expunge(44).unwrap(),
+            Data::flags(vec![
+                Flag::Answered,
+                Flag::Seen,
+                Flag::Keyword("$Junk".try_into().unwrap()),
+            ]),
+            Data::search(vec![
+                NonZeroU32::new(2).unwrap(),
+                NonZeroU32::new(84).unwrap(),
+            ]),
+            Data::list(
+                vec![FlagNameAttribute::Noselect],
+                Some(QuotedChar::try_from('/').unwrap()),
+                "Archive",
+            )
+            .unwrap(),
+            Data::lsub(vec![], None, "INBOX").unwrap(),
+            Data::status(
+                "Drafts",
+                vec![StatusDataItem::Messages(3), StatusDataItem::Unseen(1)],
+            )
+            .unwrap(),
+            Data::fetch(
+                12,
+                vec![
+                    MessageDataItem::Flags(vec![FlagFetch::Flag(Flag::Seen)]),
+                    MessageDataItem::Rfc822Size(44827),
+                ],
+            )
+            .unwrap(),
+        ];
+
+        for data in tests {
+            let response = Response::Data(data);
+            let encoded = ResponseCodec::default().encode(&response).dump();
+            let (remaining, decoded) = ResponseCodec::default().decode(&encoded).unwrap();
+
+            assert!(remaining.is_empty());
+            assert_eq!(decoded, response);
+        }
+    }
+
     #[test]
     fn test_encode_body_structure() {
         let tests = [
@@ -712,6 +1145,136 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_resp_cond_state_unknown_status() {
+        assert!(resp_cond_state(b"MAYBE foo\r\n").is_err());
+        assert!(response_tagged(b"A1 MAYBE foo\r\n").is_err());
+        assert_eq!(
+            ResponseCodec::default().decode(b"A1 MAYBE foo\r\n"),
+            Err(ResponseDecodeError::Failed)
+        );
+
+        // A status word must be followed by a space.
+        assert!(resp_cond_state(b"OKAY foo\r\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_resp_text_stops_at_crlf() {
+        let (rem, (code, text)) = resp_text(b"[ALERT] a\tb [c]\r\n* OK\r\n").unwrap();
+        assert_eq!(rem, b"\r\n* OK\r\n");
+        assert_eq!(code, Some(Code::Alert));
+        assert_eq!(text.as_ref(), "a\tb [c]");
+
+        let (rem, (code, text)) = resp_text(b"Hello [world\r\n").unwrap();
+        assert_eq!(rem, b"\r\n");
+        assert_eq!(code, None);
+        assert_eq!(text.as_ref(), "Hello [world");
+
+        // A leading `[` must start a code.
+        assert!(resp_text(b"[world\r\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_resp_text_code_other() {
+        let tests = [
+            (b"[MYCODE] text\r\n".as_ref(), b"MYCODE".as_ref()),
+            (b"[MYCODE a b c] text\r\n", b"MYCODE a b c"),
+            (b"[MYCODE (a \"b\")] text\r\n", b"MYCODE (a \"b\")"),
+            (b"[MYCODE [a] text\r\n", b"MYCODE [a"),
+            // Known codes with unexpected parameters are not misinterpreted.
+            (b"[UIDNEXT 5 6] text\r\n", b"UIDNEXT 5 6"),
+            (b"[ALERTX] text\r\n", b"ALERTX"),
+        ];
+
+        for (test, expected) in tests {
+            let (rem, (code, text)) = resp_text(test).unwrap();
+            assert_eq!(rem, b"\r\n");
+            match code {
+                Some(Code::Other(other)) => assert_eq!(other.inner(), expected),
+                got => panic!("expected `Code::Other`, got {got:?}"),
+            }
+            assert_eq!(text.as_ref(), "text");
+        }
+
+        // The first `]` ends the code.
+        assert!(resp_text(b"[MYCODE a]b] text\r\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_registered_code() {
+        #[derive(Debug, PartialEq)]
+        enum VendorCode {
+            Throttled(u32),
+            Maintenance,
+        }
+
+        let registry = CodeRegistry::new()
+            .register("X-THROTTLED", |params| {
+                Some(VendorCode::Throttled(
+                    std::str::from_utf8(params?).ok()?.parse().ok()?,
+                ))
+            })
+            .register("X-MAINTENANCE", |_| Some(VendorCode::Maintenance));
+
+        let tests = [
+            (
+                b"* OK [X-THROTTLED 30] Slow down\r\n".as_ref(),
+                Some(VendorCode::Throttled(30)),
+            ),
+            (
+                b"* NO [x-maintenance] Try again later\r\n",
+                Some(VendorCode::Maintenance),
+            ),
+            (b"* OK [X-THROTTLED soon] Slow down\r\n", None),
+            (b"* OK [X-UNKNOWN 30] ...\r\n", None),
+            (b"* OK [ALERT] ...\r\n", None),
+        ];
+
+        for (test, expected) in tests {
+            let (_, got) = ResponseCodec::default().decode(test).unwrap();
+            match got {
+                Response::Status(status) => {
+                    assert_eq!(registry.parse(status.code().unwrap()), expected)
+                }
+                got => panic!("expected status, got {got:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_kat_inverse_response_code_edge_cases() {
+        kat_inverse_response(&[
+            (
+                b"* OK [PERMANENTFLAGS ()] No permanent flags permitted\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Status(
+                    Status::ok(
+                        None,
+                        Some(Code::PermanentFlags(vec![])),
+                        "No permanent flags permitted",
+                    )
+                    .unwrap(),
+                ),
+            ),
+            // `UIDVALIDITY 0` violates `nz-number` and is kept as an unknown code. Thus, it can't be
+            // mistaken for a valid UIDVALIDITY.
+            (
+                b"* OK [UIDVALIDITY 0] UIDs not valid\r\n",
+                b"",
+                Response::Status(
+                    Status::ok(
+                        None,
+                        Some(Code::Other(CodeOther::unvalidated(
+                            b"UIDVALIDITY 0".as_ref(),
+                        ))),
+                        "UIDs not valid",
+                    )
+                    .unwrap(),
+                ),
+            ),
+        ]);
+    }
+
     #[test]
     fn test_parse_resp_text_quirk() {
         #[cfg(not(feature = "quirk_missing_text"))]
@@ -730,6 +1293,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_status_without_text_quirk() {
+        let tests = [
+            b"A1 OK\r\n".as_ref(),
+            b"A1 NO\r\n",
+            b"* BAD\r\n",
+            b"* BYE\r\n",
+        ];
+
+        for test in tests {
+            #[cfg(not(feature = "quirk_missing_text"))]
+            assert!(response(test).is_err());
+
+            #[cfg(feature = "quirk_missing_text")]
+            {
+                let (rem, got) = response(test).unwrap();
+                assert!(rem.is_empty());
+                assert!(matches!(
+                    got,
+                    Response::Status(status) if status.code().is_none() && status.text().as_ref() == "..."
+                ));
+            }
+        }
+
+        #[cfg(feature = "quirk_missing_text")]
+        {
+            let (_, got) = greeting(b"* OK\r\n").unwrap();
+            assert_eq!(got, Greeting::ok(None, "...").unwrap());
+
+            // Still incomplete ...
+            assert!(matches!(response(b"A1 OK"), Err(nom::Err::Incomplete(_))));
+            assert!(matches!(response(b"A1 OK\r"), Err(nom::Err::Incomplete(_))));
+        }
+    }
+
     #[test]
     fn test_parse_resp_space_quirk() {
         assert!(response_data(b"* STATUS INBOX (MESSAGES 100 UNSEEN 0)\r\n").is_ok());
@@ -31,6 +31,10 @@ use crate::{core::nz_number, decode::IMAPResult};
 /// Simplified:
 ///
 /// `sequence-set = (seq-number / seq-range) *("," (seq-number / seq-range))`
+///
+/// Note: Whitespace is not allowed inside a sequence set. Some (buggy) clients send, e.g., `1 : 5`.
+/// The parser stops in front of the whitespace, so that the enclosing command fails to parse
+/// instead of being misinterpreted.
 pub(crate) fn sequence_set(input: &[u8]) -> IMAPResult<&[u8], SequenceSet> {
     map(
         separated_list1(
@@ -84,7 +88,10 @@ pub(crate) fn seq_number(input: &[u8]) -> IMAPResult<&[u8], SeqOrUid> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::encode::{EncodeContext, EncodeIntoContext};
+    use crate::{
+        encode::{EncodeContext, EncodeIntoContext},
+        testing::assert_roundtrip,
+    };
 
     #[test]
     fn test_encode_of_some_sequence_sets() {
@@ -98,6 +105,18 @@ mod tests {
                 Sequence::Range(SeqOrUid::Value(1.try_into().unwrap()), SeqOrUid::Asterisk),
                 b"1:*".as_ref(),
             ),
+            // Degenerate ranges
+            (
+                Sequence::Range(
+                    SeqOrUid::Value(5.try_into().unwrap()),
+                    SeqOrUid::Value(5.try_into().unwrap()),
+                ),
+                b"5".as_ref(),
+            ),
+            (
+                Sequence::Range(SeqOrUid::Asterisk, SeqOrUid::Asterisk),
+                b"*".as_ref(),
+            ),
         ];
 
         for (test, expected) in tests {
@@ -109,6 +128,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sequence_set_chunks() {
+        let seq = SequenceSet::try_from(
+            (1..=1000u32)
+                .step_by(3)
+                .map(|i| format!("{i}:{}", i + 1))
+                .chain(["*".into(), "4294967295".into(), "5:*".into()])
+                .collect::<Vec<_>>()
+                .join(",")
+                .as_str(),
+        )
+        .unwrap();
+
+        for max_encoded_len in [1, 10, 11, 50, 999, 100_000] {
+            let mut joined = Vec::new();
+
+            for chunk in seq.chunks(max_encoded_len) {
+                let mut ctx = EncodeContext::new();
+                chunk.encode_ctx(&mut ctx).unwrap();
+                let out = ctx.dump();
+
+                // Only a single sequence may exceed the limit.
+                assert!(out.len() <= max_encoded_len || chunk.0.as_ref().len() == 1);
+                joined.extend(chunk.0);
+            }
+
+            assert_eq!(joined, seq.0.as_ref());
+        }
+    }
+
     #[test]
     fn test_parse_sequence_set() {
         let (rem, val) = sequence_set(b"1:*?").unwrap();
@@ -118,6 +167,75 @@ mod tests {
         println!("{:?}, {:?}", rem, val);
     }
 
+    #[test]
+    fn test_parse_sequence_set_degenerate_ranges() {
+        // Degenerate ranges are parsed as written (and only normalized by the encoder).
+        let (rem, val) = sequence_set(b"5:5,*:*,1:2?").unwrap();
+        assert_eq!(rem, b"?");
+        assert_eq!(
+            val.0.as_ref(),
+            [
+                Sequence::Range(
+                    SeqOrUid::Value(5.try_into().unwrap()),
+                    SeqOrUid::Value(5.try_into().unwrap())
+                ),
+                Sequence::Range(SeqOrUid::Asterisk, SeqOrUid::Asterisk),
+                Sequence::Range(
+                    SeqOrUid::Value(1.try_into().unwrap()),
+                    SeqOrUid::Value(2.try_into().unwrap())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_sequence_set_rejects_whitespace() {
+        let tests = [
+            (b"1 :5".as_ref(), b" :5".as_ref()),
+            (b"1: 5", b": 5"),
+            (b"1 ,5", b" ,5"),
+        ];
+
+        for (test, expected_rem) in tests {
+            let (rem, val) = sequence_set(test).unwrap();
+            assert_eq!(rem, expected_rem);
+            assert_eq!(
+                val.0.as_ref(),
+                [Sequence::Single(SeqOrUid::Value(1.try_into().unwrap()))]
+            );
+
+            let mut command = b"A FETCH ".to_vec();
+            command.extend_from_slice(test);
+            command.extend_from_slice(b" FLAGS\r\n");
+            assert!(crate::command::command(&command).is_err());
+
+            let test = std::str::from_utf8(test).unwrap();
+            assert!(SequenceSet::try_from(test).is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_sequence_set_strict() {
+        for (test, strict) in [
+            (b"1:5,7,9:*?".as_ref(), true),
+            (b"5:5,6?", true),
+            (b"*:1?", true),
+            (b"1:1000000,1:1000000?", false),
+            (b"3,2?", false),
+        ] {
+            let (rem, val) = sequence_set(test).unwrap();
+            assert_eq!(rem, b"?");
+            assert_eq!(val.validate_strict().is_ok(), strict);
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_sequence_set() {
+        for test in ["1", "*", "1:*", "*:1", "1,2:3,*,4294967295:5"] {
+            assert_roundtrip!(SequenceSet::try_from(test).unwrap(), sequence_set);
+        }
+    }
+
     #[test]
     fn test_parse_seq_number() {
         // Must not be 0.
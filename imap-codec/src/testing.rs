@@ -48,6 +48,38 @@ pub(crate) fn known_answer_test_parse<'a, O, P>(
     assert_eq!(expected_object, got_object);
 }
 
+/// Encode `object`, parse it again using `parser`, and compare the result with `object`.
+///
+/// Parsers are streaming parsers, thus, a `\r\n` is appended to the encoded bytes to mark the end
+/// of the input. The parser must consume everything but the `\r\n`.
+///
+/// Note: This is a macro (and not a function) because the parsed object borrows from the local
+/// buffer holding the encoded bytes. A function can't name the lifetime of this buffer.
+macro_rules! assert_roundtrip {
+    ($object:expr, $parser:expr $(,)?) => {{
+        use crate::encode::{EncodeContext, EncodeIntoContext};
+
+        let object = $object;
+
+        let mut ctx = EncodeContext::new();
+        object.encode_ctx(&mut ctx).unwrap();
+
+        let mut encoded = ctx.dump();
+        encoded.extend_from_slice(b"\r\n");
+
+        let (got_remainder, got_object) = $parser(&encoded).unwrap_or_else(|error| {
+            panic!(
+                "parsing of <<<{}>>> failed: {error:?}",
+                imap_types::utils::escape_byte_string(&encoded)
+            )
+        });
+        assert_eq!(object, got_object);
+        assert_eq!(got_remainder, b"\r\n");
+    }};
+}
+
+pub(crate) use assert_roundtrip;
+
 // Note: Maybe there is a cleaner way to write this using generic bounds. However,
 // we tried it and failed to provide a cleaner solution. Thus, it's a macro for now.
 macro_rules! impl_kat_inverse {
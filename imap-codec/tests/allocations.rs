@@ -0,0 +1,71 @@
+//! Count the allocations made when decoding responses.
+//!
+//! Known flags and capabilities (including their parameters, e.g., `AUTH=PLAIN`) are matched
+//! case-insensitively without allocating. Thus, decoding only allocates for the `Vec` holding the
+//! items.
+//!
+//! Note: All measurements live in a single test because the allocation counter is global.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use imap_codec::{decode::Decoder, ResponseCodec};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn count_allocations(input: &[u8]) -> usize {
+    let codec = ResponseCodec::default();
+
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    let (rem, response) = codec.decode(input).unwrap();
+    let after = ALLOCATIONS.load(Ordering::SeqCst);
+
+    assert!(rem.is_empty());
+    drop(response);
+
+    after - before
+}
+
+#[test]
+fn test_allocations_decode_response() {
+    // 40 capabilities in mixed case.
+    let capability = b"* CAPABILITY IMAP4rev1 imap4REV1 IDLE idle SASL-IR sasl-ir ENABLE enable \
+QUOTA quota QUOTASET quotaset LITERAL+ literal+ LITERAL- literal- MOVE move UNSELECT unselect \
+AUTH=PLAIN auth=plain AUTH=LOGIN auth=login AUTH=XOAUTH2 auth=xoauth2 COMPRESS=DEFLATE \
+compress=deflate QUOTA=RES-STORAGE quota=res-storage QUOTA=RES-MESSAGE quota=res-message X-FOO \
+x-foo X-BAR x-bar X-BAZ x-baz X-QUX x-qux\r\n";
+    // 5 system flags and 4 keywords.
+    let flags =
+        b"* FLAGS (\\Answered \\Flagged \\deleted \\SEEN \\Draft $Forwarded $Junk $NotJunk \
+Important)\r\n";
+
+    // Only the `Vec` grows, i.e., 4 -> 8 -> 16 -> 32 -> 64 and 4 -> 8 -> 16 items.
+    let got = count_allocations(capability);
+    assert!(got <= 5, "CAPABILITY: {got} allocations");
+
+    let got = count_allocations(flags);
+    assert!(got <= 3, "FLAGS: {got} allocations");
+}
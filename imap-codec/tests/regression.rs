@@ -0,0 +1,62 @@
+//! Regression tests using a corpus of tricky messages.
+//!
+//! Every file in `tests/fixtures/{commands,responses}` contains exactly one message.
+//! Each message must parse completely, and must parse to the same value again after encoding.
+
+use std::{fs, path::PathBuf};
+
+use imap_codec::{decode::Decoder, encode::Encoder, CommandCodec, ResponseCodec};
+
+fn fixtures(kind: &str) -> Vec<(PathBuf, Vec<u8>)> {
+    let directory = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(kind);
+
+    let mut fixtures: Vec<_> = fs::read_dir(&directory)
+        .unwrap()
+        .map(|entry| {
+            let path = entry.unwrap().path();
+            let data = fs::read(&path).unwrap();
+            (path, data)
+        })
+        .collect();
+    fixtures.sort();
+
+    assert!(!fixtures.is_empty(), "no fixtures in {directory:?}");
+
+    fixtures
+}
+
+#[test]
+fn test_regression_commands() {
+    for (path, data) in fixtures("commands") {
+        let (rem, parsed) = CommandCodec::default()
+            .decode(&data)
+            .unwrap_or_else(|error| panic!("{path:?}: {error:?}"));
+        assert!(rem.is_empty(), "{path:?}: remaining {rem:?}");
+
+        let encoded = CommandCodec::default().encode(&parsed).dump();
+        let (rem, parsed2) = CommandCodec::default()
+            .decode(&encoded)
+            .unwrap_or_else(|error| panic!("{path:?} (encoded): {error:?}"));
+        assert!(rem.is_empty(), "{path:?} (encoded): remaining {rem:?}");
+        assert_eq!(parsed, parsed2, "{path:?}");
+    }
+}
+
+#[test]
+fn test_regression_responses() {
+    for (path, data) in fixtures("responses") {
+        let (rem, parsed) = ResponseCodec::default()
+            .decode(&data)
+            .unwrap_or_else(|error| panic!("{path:?}: {error:?}"));
+        assert!(rem.is_empty(), "{path:?}: remaining {rem:?}");
+
+        let encoded = ResponseCodec::default().encode(&parsed).dump();
+        let (rem, parsed2) = ResponseCodec::default()
+            .decode(&encoded)
+            .unwrap_or_else(|error| panic!("{path:?} (encoded): {error:?}"));
+        assert!(rem.is_empty(), "{path:?} (encoded): remaining {rem:?}");
+        assert_eq!(parsed, parsed2, "{path:?}");
+    }
+}
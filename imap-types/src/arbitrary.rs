@@ -7,8 +7,8 @@ use crate::{
         BasicFields, Body, BodyExtension, BodyStructure, SinglePartExtensionData, SpecificFields,
     },
     core::{
-        AString, Atom, AtomExt, IString, Literal, LiteralMode, NString, Quoted, QuotedChar, Tag,
-        Text, Vec1, Vec2,
+        AString, Atom, AtomExt, Charset, IString, Literal, LiteralMode, NString, Quoted,
+        QuotedChar, Tag, Text, Vec1, Vec2,
     },
     datetime::{DateTime, NaiveDate},
     extensions::{enable::CapabilityEnable, quota::Resource},
@@ -19,7 +19,7 @@ use crate::{
         Status, StatusBody, StatusKind, Tagged,
     },
     search::SearchKey,
-    sequence::SequenceSet,
+    sequence::{SeqOrUid, Sequence, SequenceSet},
 };
 #[cfg(not(feature = "arbitrary_simplified"))]
 use crate::{body::MultiPartExtensionData, envelope::Envelope};
@@ -58,6 +58,7 @@ macro_rules! impl_arbitrary_try_from_t {
 impl_arbitrary_try_from! { Atom<'a>, &str }
 impl_arbitrary_try_from! { AtomExt<'a>, &str }
 impl_arbitrary_try_from! { Quoted<'a>, &str }
+impl_arbitrary_try_from! { Charset<'a>, &str }
 impl_arbitrary_try_from! { Tag<'a>, &str }
 impl_arbitrary_try_from! { Text<'a>, &str }
 impl_arbitrary_try_from! { ListCharString<'a>, &str }
@@ -80,6 +81,18 @@ impl<'a> Arbitrary<'a> for CommandContinuationRequestBasic<'a> {
     }
 }
 
+// A degenerate range, e.g., `5:5`, is encoded as a single number and thus not generated.
+impl<'a> Arbitrary<'a> for Sequence {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let from = SeqOrUid::arbitrary(u)?;
+
+        Ok(match Option::<SeqOrUid>::arbitrary(u)? {
+            Some(to) if to != from => Sequence::Range(from, to),
+            _ => Sequence::Single(from),
+        })
+    }
+}
+
 // TODO(#301): This is due to the `Code`/`Text` ambiguity.
 impl<'a> Arbitrary<'a> for Greeting<'a> {
     fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
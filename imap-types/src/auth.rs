@@ -17,6 +17,7 @@ use crate::{
     core::{impl_try_from, Atom},
     error::ValidationError,
     secret::Secret,
+    utils::to_ascii_lowercase_in,
 };
 
 /// Authentication mechanism.
@@ -86,6 +87,60 @@ pub enum AuthMechanism<'a> {
     /// * <https://developers.google.com/gmail/imap/xoauth2-protocol>
     XOAuth2,
 
+    /// The ANONYMOUS SASL mechanism.
+    ///
+    /// ```imap
+    /// AUTH=ANONYMOUS
+    /// ```
+    ///
+    /// # Reference(s):
+    ///
+    /// * <https://datatracker.ietf.org/doc/html/rfc4505>
+    Anonymous,
+
+    /// The EXTERNAL SASL mechanism, e.g., for TLS client certificates.
+    ///
+    /// ```imap
+    /// AUTH=EXTERNAL
+    /// ```
+    ///
+    /// # Reference(s):
+    ///
+    /// * <https://datatracker.ietf.org/doc/html/rfc4422#appendix-A>
+    External,
+
+    /// The GSSAPI (Kerberos V5) SASL mechanism.
+    ///
+    /// ```imap
+    /// AUTH=GSSAPI
+    /// ```
+    ///
+    /// # Reference(s):
+    ///
+    /// * <https://datatracker.ietf.org/doc/html/rfc4752>
+    GssApi,
+
+    //
+    // --- MD5 ---
+    //
+    /// CRAM-MD5
+    ///
+    /// Note: Not recommended for new deployments.
+    ///
+    /// # Reference(s):
+    ///
+    /// * <https://datatracker.ietf.org/doc/html/rfc2195>
+    CramMd5,
+
+    /// DIGEST-MD5
+    ///
+    /// Note: Moved to historic by RFC 6331.
+    ///
+    /// # Reference(s):
+    ///
+    /// * <https://datatracker.ietf.org/doc/html/rfc2831>
+    DigestMd5,
+
     //
     // --- SHA-1 ---
     //
@@ -149,20 +204,60 @@ impl_try_from!(Atom<'a>, 'a, Cow<'a, str>, AuthMechanism<'a>);
 
 impl<'a> From<Atom<'a>> for AuthMechanism<'a> {
     fn from(atom: Atom<'a>) -> Self {
-        match atom.as_ref().to_ascii_uppercase().as_str() {
-            "PLAIN" => Self::Plain,
-            "LOGIN" => Self::Login,
-            "OAUTHBEARER" => Self::OAuthBearer,
-            "XOAUTH2" => Self::XOAuth2,
-            "SCRAM-SHA-1" => Self::ScramSha1,
-            "SCRAM-SHA-1-PLUS" => Self::ScramSha1Plus,
-            "SCRAM-SHA-256" => Self::ScramSha256,
-            "SCRAM-SHA-256-PLUS" => Self::ScramSha256Plus,
+        let mut buffer = [0u8; 19];
+
+        match to_ascii_lowercase_in(atom.as_ref(), &mut buffer).unwrap_or_default() {
+            "plain" => Self::Plain,
+            "login" => Self::Login,
+            "oauthbearer" => Self::OAuthBearer,
+            "xoauth2" => Self::XOAuth2,
+            "anonymous" => Self::Anonymous,
+            "external" => Self::External,
+            "gssapi" => Self::GssApi,
+            "cram-md5" => Self::CramMd5,
+            "digest-md5" => Self::DigestMd5,
+            "scram-sha-1" => Self::ScramSha1,
+            "scram-sha-1-plus" => Self::ScramSha1Plus,
+            "scram-sha-256" => Self::ScramSha256,
+            "scram-sha-256-plus" => Self::ScramSha256Plus,
+            "scram-sha3-512" => Self::ScramSha3_512,
+            "scram-sha3-512-plus" => Self::ScramSha3_512Plus,
             _ => Self::Other(AuthMechanismOther(atom)),
         }
     }
 }
 
+impl AuthMechanism<'_> {
+    /// Build the initial response of the [`AuthMechanism::XOAuth2`] mechanism.
+    ///
+    /// The result is meant to be passed to [`CommandBody::authenticate_with_ir`](crate::command::CommandBody::authenticate_with_ir),
+    /// i.e., it is *not* base64-encoded (this is done during encoding).
+    ///
+    /// ```text
+    /// b"user=<user>\x01auth=Bearer <token>\x01\x01"
+    /// ```
+    pub fn xoauth2_initial_response(user: &str, token: &str) -> Vec<u8> {
+        format!("user={user}\x01auth=Bearer {token}\x01\x01").into_bytes()
+    }
+
+    /// Build the initial response of the [`AuthMechanism::OAuthBearer`] mechanism (RFC 7628).
+    ///
+    /// The result is meant to be passed to [`CommandBody::authenticate_with_ir`](crate::command::CommandBody::authenticate_with_ir),
+    /// i.e., it is *not* base64-encoded (this is done during encoding).
+    ///
+    /// ```text
+    /// b"n,a=<user>,\x01host=<host>\x01port=<port>\x01auth=Bearer <token>\x01\x01"
+    /// ```
+    ///
+    /// Note: `,` and `=` in `user` are escaped as required by the GS2 header.
+    pub fn oauthbearer_initial_response(user: &str, host: &str, port: u16, token: &str) -> Vec<u8> {
+        let user = user.replace('=', "=3D").replace(',', "=2C");
+
+        format!("n,a={user},\x01host={host}\x01port={port}\x01auth=Bearer {token}\x01\x01")
+            .into_bytes()
+    }
+}
+
 impl<'a> Display for AuthMechanism<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_str(self.as_ref())
@@ -176,6 +271,11 @@ impl<'a> AsRef<str> for AuthMechanism<'a> {
             Self::Login => "LOGIN",
             Self::OAuthBearer => "OAUTHBEARER",
             Self::XOAuth2 => "XOAUTH2",
+            Self::Anonymous => "ANONYMOUS",
+            Self::External => "EXTERNAL",
+            Self::GssApi => "GSSAPI",
+            Self::CramMd5 => "CRAM-MD5",
+            Self::DigestMd5 => "DIGEST-MD5",
             Self::ScramSha1 => "SCRAM-SHA-1",
             Self::ScramSha1Plus => "SCRAM-SHA-1-PLUS",
             Self::ScramSha256 => "SCRAM-SHA-256",
@@ -243,4 +343,72 @@ mod tests {
         assert!(AuthMechanism::try_from("xxxlogin").is_ok());
         assert!(AuthMechanism::try_from("xxxxoauth2").is_ok());
     }
+
+    #[test]
+    fn test_known_mechanisms() {
+        let tests = [
+            ("PLAIN", AuthMechanism::Plain),
+            ("LOGIN", AuthMechanism::Login),
+            ("OAUTHBEARER", AuthMechanism::OAuthBearer),
+            ("XOAUTH2", AuthMechanism::XOAuth2),
+            ("ANONYMOUS", AuthMechanism::Anonymous),
+            ("EXTERNAL", AuthMechanism::External),
+            ("GSSAPI", AuthMechanism::GssApi),
+            ("CRAM-MD5", AuthMechanism::CramMd5),
+            ("DIGEST-MD5", AuthMechanism::DigestMd5),
+            ("SCRAM-SHA-1", AuthMechanism::ScramSha1),
+            ("SCRAM-SHA-1-PLUS", AuthMechanism::ScramSha1Plus),
+            ("SCRAM-SHA-256", AuthMechanism::ScramSha256),
+            ("SCRAM-SHA-256-PLUS", AuthMechanism::ScramSha256Plus),
+            ("SCRAM-SHA3-512", AuthMechanism::ScramSha3_512),
+            ("SCRAM-SHA3-512-PLUS", AuthMechanism::ScramSha3_512Plus),
+        ];
+
+        for (name, expected) in tests {
+            assert_eq!(AuthMechanism::try_from(name).unwrap(), expected);
+            assert_eq!(
+                AuthMechanism::try_from(name.to_ascii_lowercase()).unwrap(),
+                expected
+            );
+            assert_eq!(expected.as_ref(), name);
+        }
+    }
+
+    #[test]
+    fn test_oauth_initial_responses() {
+        // https://developers.google.com/gmail/imap/xoauth2-protocol
+        assert_eq!(
+            AuthMechanism::xoauth2_initial_response(
+                "someuser@example.com",
+                "ya29.vF9dft4qmTc2Nvb3RlckBhdHRhdmlzdGEuY29tCg"
+            ),
+            b"user=someuser@example.com\x01auth=Bearer ya29.vF9dft4qmTc2Nvb3RlckBhdHRhdmlzdGEuY29tCg\x01\x01"
+        );
+
+        // RFC 7628, section 4.1
+        assert_eq!(
+            AuthMechanism::oauthbearer_initial_response(
+                "user@example.com",
+                "server.example.com",
+                143,
+                "vF9dft4qmTc2Nvb3RlckBhbHRhdmlzdGEuY29tCg=="
+            ),
+            b"n,a=user@example.com,\x01host=server.example.com\x01port=143\x01auth=Bearer vF9dft4qmTc2Nvb3RlckBhbHRhdmlzdGEuY29tCg==\x01\x01"
+        );
+
+        assert!(
+            AuthMechanism::oauthbearer_initial_response("a,b=c", "host", 993, "token")
+                .starts_with(b"n,a=a=2Cb=3Dc,\x01")
+        );
+    }
+
+    #[test]
+    fn test_unknown_mechanism() {
+        let mechanism = AuthMechanism::try_from("X-Unknown").unwrap();
+
+        assert!(matches!(mechanism, AuthMechanism::Other(_)));
+        // The original spelling is preserved.
+        assert_eq!(mechanism.as_ref(), "X-Unknown");
+        assert_eq!(mechanism.to_string(), "X-Unknown");
+    }
 }
@@ -0,0 +1,185 @@
+//! Case-sensitivity rules of IMAP names.
+//!
+//! IMAP is inconsistent about casing: Some names are compared case-insensitively, others are not.
+//! The functions in this module implement these rules and should be preferred over `==` when
+//! comparing names received from another party.
+//!
+//! Note: Known names, e.g., `\Seen`, `\Noselect`, or `IMAP4rev1`, are already recognized
+//! case-insensitively when a [`Flag`], [`FlagNameAttribute`](crate::flag::FlagNameAttribute), or
+//! [`Capability`] is constructed. Thus, `==` works for these. The functions here are only
+//! required for names that are kept as-is, e.g., keywords or unknown capabilities.
+//!
+//! | Name                                      | Case-sensitive? |
+//! |-------------------------------------------|-----------------|
+//! | System flags, e.g., `\Seen`               | No              |
+//! | Keywords, e.g., `$Forwarded`              | Yes             |
+//! | Capabilities, e.g., `IMAP4rev1`           | No              |
+//! | `INBOX`                                   | No              |
+//! | Other mailboxes, e.g., `Archive`          | Yes             |
+
+use std::{
+    cmp::Ordering,
+    fmt::{Display, Write},
+};
+
+#[cfg(feature = "ext_sort_thread")]
+use crate::extensions::{sort::SortAlgorithm, thread::ThreadingAlgorithm};
+use crate::{
+    extensions::quota::Resource,
+    flag::{canonical_cmp, Flag},
+    mailbox::Mailbox,
+    response::Capability,
+};
+
+/// Compares two flags.
+///
+/// System flags (`\Seen`, ...) and flag extensions (`\Foo`) are compared case-insensitively,
+/// keywords (`$Forwarded`, `Foo`, ...) are compared case-sensitively.
+pub fn eq_flag(a: &Flag, b: &Flag) -> bool {
+    canonical_cmp(a, b) == Ordering::Equal
+}
+
+/// Compares two capabilities case-insensitively.
+///
+/// This includes parameters, e.g., `AUTH=plain` equals `AUTH=PLAIN`.
+pub fn eq_capability(a: &Capability, b: &Capability) -> bool {
+    match (a, b) {
+        (Capability::Auth(a), Capability::Auth(b)) => a.as_ref().eq_ignore_ascii_case(b.as_ref()),
+        (Capability::QuotaRes(Resource::Other(a)), Capability::QuotaRes(Resource::Other(b))) => {
+            a.as_ref().eq_ignore_ascii_case(b.as_ref())
+        }
+        #[cfg(feature = "ext_sort_thread")]
+        (
+            Capability::Sort(Some(SortAlgorithm::Other(a))),
+            Capability::Sort(Some(SortAlgorithm::Other(b))),
+        ) => a.as_ref().eq_ignore_ascii_case(b.as_ref()),
+        #[cfg(feature = "ext_sort_thread")]
+        (
+            Capability::Thread(ThreadingAlgorithm::Other(a)),
+            Capability::Thread(ThreadingAlgorithm::Other(b)),
+        ) => a.as_ref().eq_ignore_ascii_case(b.as_ref()),
+        (Capability::Other(a), Capability::Other(b)) => a.as_ref().eq_ignore_ascii_case(b.as_ref()),
+        // An unvalidated `Other` may still spell a known capability.
+        (Capability::Other(other), known) | (known, Capability::Other(other)) => {
+            display_eq_ignore_ascii_case(known, other.as_ref())
+        }
+        _ => a == b,
+    }
+}
+
+/// Compares the `Display` output of `value` with `other` (without allocating).
+fn display_eq_ignore_ascii_case(value: &impl Display, other: &str) -> bool {
+    struct Matcher<'s>(&'s str);
+
+    impl Write for Matcher<'_> {
+        fn write_str(&mut self, chunk: &str) -> std::fmt::Result {
+            match (self.0.get(..chunk.len()), self.0.get(chunk.len()..)) {
+                (Some(prefix), Some(rest)) if prefix.eq_ignore_ascii_case(chunk) => {
+                    self.0 = rest;
+                    Ok(())
+                }
+                _ => Err(std::fmt::Error),
+            }
+        }
+    }
+
+    let mut matcher = Matcher(other);
+
+    write!(matcher, "{value}").is_ok() && matcher.0.is_empty()
+}
+
+/// Returns `true` when `name` is `INBOX` (in any case).
+pub(crate) fn is_inbox(name: &[u8]) -> bool {
+    name.eq_ignore_ascii_case(b"inbox")
+}
+
+/// Compares two mailboxes.
+///
+/// `INBOX` is compared case-insensitively, all other mailbox names are compared case-sensitively.
+pub fn eq_mailbox(a: &Mailbox, b: &Mailbox) -> bool {
+    fn is_inbox_mailbox(mailbox: &Mailbox) -> bool {
+        match mailbox {
+            Mailbox::Inbox => true,
+            // An unvalidated mailbox may still be named `inbox`.
+            Mailbox::Other(other) => is_inbox(other.as_ref()),
+        }
+    }
+
+    match (a, b) {
+        (Mailbox::Other(a), Mailbox::Other(b)) if a.as_ref() == b.as_ref() => true,
+        _ => is_inbox_mailbox(a) && is_inbox_mailbox(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::AuthMechanism;
+
+    #[test]
+    fn test_eq_flag() {
+        let flag = |name: &'static str| Flag::try_from(name).unwrap();
+
+        assert!(eq_flag(&flag("\\Seen"), &flag("\\SEEN")));
+        assert!(eq_flag(&flag("\\Foo"), &flag("\\fOO")));
+        assert!(eq_flag(&flag("$Forwarded"), &flag("$Forwarded")));
+        assert!(!eq_flag(&flag("$Forwarded"), &flag("$forwarded")));
+        assert!(!eq_flag(&flag("\\Seen"), &flag("Seen")));
+        assert!(!eq_flag(&flag("\\Foo"), &flag("Foo")));
+    }
+
+    #[test]
+    fn test_eq_capability() {
+        let capability = |name: &'static str| Capability::try_from(name).unwrap();
+
+        assert!(eq_capability(
+            &capability("imap4rev1"),
+            &capability("IMAP4REV1")
+        ));
+        assert!(eq_capability(&capability("X-FOO"), &capability("x-foo")));
+        assert!(eq_capability(
+            &capability("AUTH=xfoo"),
+            &Capability::Auth(AuthMechanism::try_from("XFOO").unwrap())
+        ));
+        assert!(!eq_capability(&capability("IDLE"), &capability("MOVE")));
+        assert!(!eq_capability(
+            &capability("X-FOO"),
+            &capability("X-FOOBAR")
+        ));
+        assert!(eq_capability(
+            &capability("QUOTA=RES-x-foo"),
+            &capability("quota=res-X-FOO")
+        ));
+    }
+
+    #[test]
+    fn test_display_eq_ignore_ascii_case() {
+        assert!(display_eq_ignore_ascii_case(
+            &Capability::Imap4Rev1,
+            "imap4REV1"
+        ));
+        assert!(display_eq_ignore_ascii_case(
+            &Capability::Auth(AuthMechanism::Plain),
+            "auth=plain"
+        ));
+        assert!(!display_eq_ignore_ascii_case(
+            &Capability::Imap4Rev1,
+            "IMAP4"
+        ));
+        assert!(!display_eq_ignore_ascii_case(
+            &Capability::Imap4Rev1,
+            "IMAP4REV1X"
+        ));
+        assert!(!display_eq_ignore_ascii_case(&Capability::Idle, "ıdle"));
+    }
+
+    #[test]
+    fn test_eq_mailbox() {
+        let mailbox = |name: &'static str| Mailbox::try_from(name).unwrap();
+
+        assert!(eq_mailbox(&mailbox("INBOX"), &mailbox("inbox")));
+        assert!(eq_mailbox(&mailbox("Archive"), &mailbox("Archive")));
+        assert!(!eq_mailbox(&mailbox("Archive"), &mailbox("archive")));
+        assert!(!eq_mailbox(&mailbox("INBOX"), &mailbox("INBOX.Archive")));
+    }
+}
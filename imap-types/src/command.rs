@@ -15,22 +15,30 @@ use serde::{Deserialize, Serialize};
 use crate::core::{IString, NString};
 #[cfg(feature = "ext_binary")]
 use crate::extensions::binary::LiteralOrLiteral8;
+#[cfg(feature = "ext_gmail")]
+use crate::extensions::gmail::GmailLabel;
 #[cfg(feature = "ext_metadata")]
 use crate::extensions::metadata::{Entry, EntryValue, GetMetadataOption};
 #[cfg(feature = "ext_sort_thread")]
 use crate::extensions::{sort::SortCriterion, thread::ThreadingAlgorithm};
+#[cfg(feature = "ext_replace")]
+use crate::sequence::SeqOrUid;
 use crate::{
     auth::AuthMechanism,
     command::error::{AppendError, CopyError, ListError, LoginError, RenameError},
     core::{AString, Charset, Literal, Tag, Vec1},
     datetime::DateTime,
-    extensions::{compress::CompressionAlgorithm, enable::CapabilityEnable, quota::QuotaSet},
+    extensions::{
+        compress::CompressionAlgorithm, condstore_qresync::SelectParameter,
+        enable::CapabilityEnable, quota::QuotaSet, special_use::CreateParameter,
+    },
     fetch::MacroOrMessageDataItemNames,
     flag::{Flag, StoreResponse, StoreType},
     mailbox::{ListMailbox, Mailbox},
     search::SearchKey,
     secret::Secret,
     sequence::SequenceSet,
+    state::State,
     status::StatusDataItemName,
 };
 
@@ -124,6 +132,9 @@ pub enum CommandBody<'a> {
     /// message status updates during a period of inactivity (this is the
     /// preferred method to do this).  The NOOP command can also be used
     /// to reset any inactivity autologout timer on the server.
+    ///
+    /// Note: The status updates are sent as untagged data *before* the tagged OK. Clients should
+    /// collect all untagged data until the tagged OK arrives and apply it to their mailbox state.
     Noop,
 
     /// ### 6.1.3.  LOGOUT Command
@@ -392,6 +403,10 @@ pub enum CommandBody<'a> {
     Select {
         /// Mailbox.
         mailbox: Mailbox<'a>,
+        /// Parameters (RFC 4466), e.g., `CONDSTORE`.
+        ///
+        /// Empty when no parameters are used.
+        parameters: Vec<SelectParameter>,
     },
 
     /// Unselect a mailbox.
@@ -421,6 +436,10 @@ pub enum CommandBody<'a> {
     Examine {
         /// Mailbox.
         mailbox: Mailbox<'a>,
+        /// Parameters (RFC 4466), e.g., `CONDSTORE`.
+        ///
+        /// Empty when no parameters are used.
+        parameters: Vec<SelectParameter>,
     },
 
     /// ### 6.3.3.  CREATE Command
@@ -469,6 +488,10 @@ pub enum CommandBody<'a> {
     Create {
         /// Mailbox.
         mailbox: Mailbox<'a>,
+        /// Parameters (RFC 4466), e.g., `USE (\Archive)`.
+        ///
+        /// Empty when no parameters are used.
+        parameters: Vec<CreateParameter<'a>>,
     },
 
     /// 6.3.4.  DELETE Command
@@ -1456,6 +1479,8 @@ pub enum CommandBody<'a> {
     /// ID command.
     Id {
         /// Parameters.
+        ///
+        /// `None` is encoded as `NIL`, whereas `Some(vec![])` is encoded as `()`.
         parameters: Option<Vec<(IString<'a>, NString<'a>)>>,
     },
 
@@ -1471,6 +1496,45 @@ pub enum CommandBody<'a> {
         mailbox: Mailbox<'a>,
         entries: Vec1<Entry<'a>>,
     },
+
+    #[cfg(feature = "ext_gmail")]
+    /// STORE command changing Gmail labels (`X-GM-LABELS`) instead of flags.
+    StoreGmailLabels {
+        /// Set of messages.
+        sequence_set: SequenceSet,
+        /// Kind of storage, i.e., replace, add, or remove.
+        kind: StoreType,
+        /// Kind of response, i.e., answer or silent.
+        response: StoreResponse,
+        /// Labels.
+        labels: Vec<GmailLabel<'a>>,
+        /// Use UID variant.
+        uid: bool,
+    },
+
+    #[cfg(feature = "ext_replace")]
+    /// REPLACE command.
+    ///
+    /// Atomically appends a message to a mailbox (like APPEND) and removes the replaced message
+    /// from the currently selected mailbox.
+    Replace {
+        /// Sequence number (or UID) of the message to replace.
+        sequence: SeqOrUid,
+        /// Mailbox.
+        mailbox: Mailbox<'a>,
+        /// Flags.
+        flags: Vec<Flag<'a>>,
+        /// Datetime.
+        date: Option<DateTime>,
+        #[cfg(not(feature = "ext_binary"))]
+        /// Replacement message.
+        message: Literal<'a>,
+        #[cfg(feature = "ext_binary")]
+        /// Replacement message.
+        message: LiteralOrLiteral8<'a>,
+        /// Use UID variant.
+        uid: bool,
+    },
 }
 
 impl<'a> CommandBody<'a> {
@@ -1527,6 +1591,7 @@ impl<'a> CommandBody<'a> {
     {
         Ok(CommandBody::Select {
             mailbox: mailbox.try_into()?,
+            parameters: Vec::new(),
         })
     }
 
@@ -1537,6 +1602,7 @@ impl<'a> CommandBody<'a> {
     {
         Ok(CommandBody::Examine {
             mailbox: mailbox.try_into()?,
+            parameters: Vec::new(),
         })
     }
 
@@ -1547,6 +1613,7 @@ impl<'a> CommandBody<'a> {
     {
         Ok(CommandBody::Create {
             mailbox: mailbox.try_into()?,
+            parameters: Vec::new(),
         })
     }
 
@@ -1769,6 +1836,75 @@ impl<'a> CommandBody<'a> {
             Self::SetMetadata { .. } => "SETMETADATA",
             #[cfg(feature = "ext_metadata")]
             Self::GetMetadata { .. } => "GETMETADATA",
+            #[cfg(feature = "ext_replace")]
+            Self::Replace { .. } => "REPLACE",
+            #[cfg(feature = "ext_gmail")]
+            Self::StoreGmailLabels { .. } => "STORE",
+        }
+    }
+
+    /// Check if the command is valid in the given state.
+    ///
+    /// It is a protocol error to send a command in an inappropriate state. Note that no command is
+    /// valid in the greeting, logout, or IDLE states.
+    pub fn valid_in(&self, state: &State) -> bool {
+        let (not_authenticated, authenticated, selected) = match state {
+            State::NotAuthenticated => (true, false, false),
+            State::Authenticated => (false, true, false),
+            State::Selected(_) => (false, false, true),
+            State::Greeting
+            | State::Logout
+            | State::IdleAuthenticated(_)
+            | State::IdleSelected(_, _) => return false,
+        };
+
+        match self {
+            // Any state
+            Self::Capability | Self::Noop | Self::Logout => true,
+            #[cfg(feature = "ext_id")]
+            Self::Id { .. } => true,
+            // Not authenticated state
+            #[cfg(feature = "starttls")]
+            Self::StartTLS => not_authenticated,
+            Self::Authenticate { .. } | Self::Login { .. } => not_authenticated,
+            // Authenticated state (ENABLE is only valid in the authenticated state)
+            Self::Enable { .. } => authenticated,
+            Self::Select { .. }
+            | Self::Examine { .. }
+            | Self::Create { .. }
+            | Self::Delete { .. }
+            | Self::Rename { .. }
+            | Self::Subscribe { .. }
+            | Self::Unsubscribe { .. }
+            | Self::List { .. }
+            | Self::Lsub { .. }
+            | Self::Status { .. }
+            | Self::Append { .. }
+            | Self::Idle
+            | Self::Compress { .. }
+            | Self::GetQuota { .. }
+            | Self::GetQuotaRoot { .. }
+            | Self::SetQuota { .. } => authenticated || selected,
+            #[cfg(feature = "ext_metadata")]
+            Self::SetMetadata { .. } | Self::GetMetadata { .. } => authenticated || selected,
+            // Selected state
+            Self::Check
+            | Self::Close
+            | Self::Unselect
+            | Self::Expunge
+            | Self::Search { .. }
+            | Self::Fetch { .. }
+            | Self::Store { .. }
+            | Self::Copy { .. }
+            | Self::Move { .. } => selected,
+            #[cfg(feature = "ext_uidplus")]
+            Self::ExpungeUid { .. } => selected,
+            #[cfg(feature = "ext_sort_thread")]
+            Self::Sort { .. } | Self::Thread { .. } => selected,
+            #[cfg(feature = "ext_replace")]
+            Self::Replace { .. } => selected,
+            #[cfg(feature = "ext_gmail")]
+            Self::StoreGmailLabels { .. } => selected,
         }
     }
 }
@@ -1816,6 +1952,17 @@ pub mod error {
         #[error("Invalid mailbox: {0}")]
         Mailbox(M),
     }
+
+    #[cfg(feature = "ext_replace")]
+    #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum ReplaceError<S, M, D> {
+        #[error("Invalid sequence: {0}")]
+        Sequence(S),
+        #[error("Invalid mailbox: {0}")]
+        Mailbox(M),
+        #[error("Invalid data: {0}")]
+        Data(D),
+    }
 }
 
 #[cfg(test)]
@@ -2046,6 +2193,7 @@ mod tests {
             (
                 CommandBody::Select {
                     mailbox: Mailbox::Inbox,
+                    parameters: vec![],
                 },
                 "SELECT",
             ),
@@ -2053,12 +2201,14 @@ mod tests {
             (
                 CommandBody::Examine {
                     mailbox: Mailbox::Inbox,
+                    parameters: vec![],
                 },
                 "EXAMINE",
             ),
             (
                 CommandBody::Create {
                     mailbox: Mailbox::Inbox,
+                    parameters: vec![],
                 },
                 "CREATE",
             ),
@@ -2216,4 +2366,77 @@ mod tests {
             assert_eq!(test.name(), expected);
         }
     }
+
+    #[test]
+    fn test_command_body_valid_in() {
+        let states = [
+            State::Greeting,
+            State::NotAuthenticated,
+            State::Authenticated,
+            State::Selected(Mailbox::Inbox),
+            State::Logout,
+        ];
+
+        // Validity in (greeting, not authenticated, authenticated, selected, logout).
+        let tests = [
+            (CommandBody::Capability, [false, true, true, true, false]),
+            (CommandBody::Noop, [false, true, true, true, false]),
+            (CommandBody::Logout, [false, true, true, true, false]),
+            #[cfg(feature = "starttls")]
+            (CommandBody::StartTLS, [false, true, false, false, false]),
+            (
+                CommandBody::login("user", "pass").unwrap(),
+                [false, true, false, false, false],
+            ),
+            (
+                CommandBody::authenticate(AuthMechanism::Plain),
+                [false, true, false, false, false],
+            ),
+            (
+                CommandBody::Enable {
+                    capabilities: Vec1::from(CapabilityEnable::Utf8(Utf8Kind::Accept)),
+                },
+                [false, false, true, false, false],
+            ),
+            (
+                CommandBody::select("INBOX").unwrap(),
+                [false, false, true, true, false],
+            ),
+            (
+                CommandBody::list("", "*").unwrap(),
+                [false, false, true, true, false],
+            ),
+            (CommandBody::Idle, [false, false, true, true, false]),
+            (CommandBody::Check, [false, false, false, true, false]),
+            (CommandBody::Close, [false, false, false, true, false]),
+            (CommandBody::Unselect, [false, false, false, true, false]),
+            (CommandBody::Expunge, [false, false, false, true, false]),
+            (
+                CommandBody::fetch("1:*", Macro::All, false).unwrap(),
+                [false, false, false, true, false],
+            ),
+            (
+                CommandBody::copy("1", "INBOX", true).unwrap(),
+                [false, false, false, true, false],
+            ),
+        ];
+
+        for (command, expected) in tests {
+            for (state, expected) in states.iter().zip(expected) {
+                assert_eq!(
+                    command.valid_in(state),
+                    expected,
+                    "{} in {state:?}",
+                    command.name()
+                );
+            }
+        }
+
+        for state in [
+            State::IdleAuthenticated(Tag::try_from("A").unwrap()),
+            State::IdleSelected(Tag::try_from("A").unwrap(), Mailbox::Inbox),
+        ] {
+            assert!(!CommandBody::Noop.valid_in(&state));
+        }
+    }
 }
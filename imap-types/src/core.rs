@@ -372,6 +372,10 @@ impl<'a> AsRef<str> for AtomExt<'a> {
 /// Note: The empty string is represented as either "" (a quoted string with zero characters between
 /// double quotes) or as {0} followed by CRLF (a literal with an octet count of 0).
 ///
+/// The variant records the form that was used on the wire and is kept during encoding, i.e.,
+/// decoding and re-encoding a string yields the same bytes. The `TryFrom` implementations
+/// deterministically choose [`IString::Quoted`] when possible and fall back to [`IString::Literal`].
+///
 /// # ABNF definition
 ///
 /// ```abnf
@@ -850,6 +854,25 @@ pub struct NString<'a>(
 );
 
 impl<'a> NString<'a> {
+    /// Returns `true` for `NIL`.
+    ///
+    /// Note: An empty string (`""`) is not `NIL`.
+    pub fn is_nil(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Get the bytes of the string, or `None` for `NIL`.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        self.0.as_ref().map(AsRef::as_ref)
+    }
+
+    /// Get the string as `&str`, or `None` for `NIL`.
+    ///
+    /// Literals may contain arbitrary bytes, thus, the conversion can fail.
+    pub fn as_str(&self) -> Option<Result<&str, std::str::Utf8Error>> {
+        self.as_bytes().map(std::str::from_utf8)
+    }
+
     pub fn into_option(self) -> Option<Cow<'a, [u8]>> {
         self.0.map(|inner| inner.into_inner())
     }
@@ -1344,7 +1367,6 @@ impl TryFrom<char> for QuotedChar {
 /// ALPHA              = "A".."Z" ; Case insensitive ASCII Letter
 /// DIGIT              = "0".."9" ; Numeric digit
 /// ```
-#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1353,6 +1375,31 @@ pub enum Charset<'a> {
     Quoted(Quoted<'a>),
 }
 
+impl<'a> Charset<'a> {
+    /// Validates if value is a non-empty atom or quoted string.
+    pub fn validate(value: impl AsRef<[u8]>) -> Result<(), ValidationError> {
+        let value = value.as_ref();
+
+        if value.is_empty() {
+            return Err(ValidationError::new(ValidationErrorKind::Empty));
+        }
+
+        if Atom::validate(value).is_ok() {
+            return Ok(());
+        }
+
+        Quoted::validate(value)
+    }
+
+    /// Compares two charsets case-insensitively.
+    ///
+    /// Charset names are case-insensitive, e.g., `UTF-8` and `"utf-8"` name the same charset.
+    /// Use this (instead of `==`) to find a charset in a server's `BADCHARSET` list.
+    pub fn matches(&self, other: &Charset) -> bool {
+        self.as_ref().eq_ignore_ascii_case(other.as_ref())
+    }
+}
+
 impl<'a> From<Atom<'a>> for Charset<'a> {
     fn from(value: Atom<'a>) -> Self {
         Self::Atom(value)
@@ -1369,6 +1416,8 @@ impl<'a> TryFrom<&'a [u8]> for Charset<'a> {
     type Error = ValidationError;
 
     fn try_from(value: &'a [u8]) -> Result<Self, Self::Error> {
+        Self::validate(value)?;
+
         if let Ok(atom) = Atom::try_from(value) {
             return Ok(Self::Atom(atom));
         }
@@ -1381,6 +1430,8 @@ impl<'a> TryFrom<Vec<u8>> for Charset<'a> {
     type Error = ValidationError;
 
     fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        Self::validate(&value)?;
+
         // TODO(efficiency)
         if let Ok(atom) = Atom::try_from(value.clone()) {
             return Ok(Self::Atom(atom));
@@ -1394,6 +1445,8 @@ impl<'a> TryFrom<&'a str> for Charset<'a> {
     type Error = ValidationError;
 
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Self::validate(value)?;
+
         if let Ok(atom) = Atom::try_from(value) {
             return Ok(Self::Atom(atom));
         }
@@ -1406,6 +1459,8 @@ impl<'a> TryFrom<String> for Charset<'a> {
     type Error = ValidationError;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::validate(&value)?;
+
         // TODO(efficiency)
         if let Ok(atom) = Atom::try_from(value.clone()) {
             return Ok(Self::Atom(atom));
@@ -1532,12 +1587,55 @@ impl<T, const N: usize> AsRef<[T]> for VecN<T, N> {
 /// The `Debug` implementation equals the standard [`Vec`] with an attached `+` at the end.
 pub type Vec1<T> = VecN<T, 1>;
 
+impl<T> Vec1<T> {
+    /// Constructs a non-empty vector from its `first` element and the `rest`.
+    ///
+    /// See also the [`vec1!`](crate::vec1) macro.
+    pub fn new<I>(first: T, rest: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut inner = vec![first];
+        inner.extend(rest);
+
+        Self(inner)
+    }
+
+    /// Constructs a non-empty vector containing a single `item`.
+    pub fn from_first(item: T) -> Self {
+        Self(vec![item])
+    }
+}
+
 impl<T> From<T> for Vec1<T> {
     fn from(value: T) -> Self {
         VecN(vec![value])
     }
 }
 
+/// Creates a [`Vec1`] containing the arguments.
+///
+/// ```rust
+/// use imap_types::{core::Vec1, vec1};
+///
+/// let vec: Vec1<u8> = vec1![1, 2, 3];
+/// assert_eq!(vec.as_ref(), [1, 2, 3]);
+/// ```
+///
+/// An empty [`Vec1`] can't be created.
+///
+/// ```rust,compile_fail
+/// use imap_types::{core::Vec1, vec1};
+///
+/// let vec: Vec1<u8> = vec1![];
+/// ```
+#[macro_export]
+macro_rules! vec1 {
+    ($first:expr $(, $rest:expr)* $(,)?) => {
+        $crate::core::Vec1::new($first, [$($rest),*])
+    };
+}
+
 /// A [`Vec`] containing >= 2 elements.
 ///
 /// The `Debug` implementation equals the standard [`Vec`] with an attached `{2,}` at the end.
@@ -1938,6 +2036,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_nstring_nil_vs_empty() {
+        let nil = NString(None);
+        assert!(nil.is_nil());
+        assert_eq!(nil.as_bytes(), None);
+        assert_eq!(nil.as_str(), None);
+
+        let empty = NString::try_from("").unwrap();
+        assert!(!empty.is_nil());
+        assert_eq!(empty.as_bytes(), Some(b"".as_ref()));
+        assert_eq!(empty.as_str(), Some(Ok("")));
+
+        let binary = NString::try_from(b"\xff".as_ref()).unwrap();
+        assert!(matches!(binary.as_str(), Some(Err(_))));
+    }
+
+    #[test]
+    fn test_charset() {
+        assert!(Charset::try_from("").is_err());
+        assert!(Charset::try_from(b"\x00".as_ref()).is_err());
+        assert_eq!(
+            Charset::try_from("UTF-8").unwrap(),
+            Charset::Atom(Atom::try_from("UTF-8").unwrap())
+        );
+        assert_eq!(
+            Charset::try_from("UTF 8".to_string()).unwrap(),
+            Charset::Quoted(Quoted::try_from("UTF 8").unwrap())
+        );
+
+        let utf8 = Charset::try_from("UTF-8").unwrap();
+        assert_ne!(utf8, Charset::try_from("utf-8").unwrap());
+        assert!(utf8.matches(&Charset::try_from("utf-8").unwrap()));
+        assert!(utf8.matches(&Charset::Quoted(Quoted::try_from("Utf-8").unwrap())));
+        assert!(!utf8.matches(&Charset::try_from("UTF-16").unwrap()));
+    }
+
     #[test]
     fn test_vec_n() {
         // Note: Don't use `VecN<T, 0>`, it's only a sanity test here.
@@ -1957,4 +2091,20 @@ mod tests {
         assert!(VecN::<u8, 2>::try_from(vec![1]).is_err());
         assert!(VecN::<u8, 2>::try_from(vec![1, 2]).is_ok());
     }
+
+    #[test]
+    fn test_vec1_constructors() {
+        assert_eq!(Vec1::new(1, []).as_ref(), [1]);
+        assert_eq!(Vec1::new(1, vec![2, 3]).as_ref(), [1, 2, 3]);
+        assert_eq!(Vec1::from_first(1).as_ref(), [1]);
+
+        assert_eq!(crate::vec1![1], Vec1::from_first(1));
+        assert_eq!(
+            crate::vec1![1, 2, 3,],
+            Vec1::try_from(vec![1, 2, 3]).unwrap()
+        );
+
+        // Note: `vec1![]` doesn't compile, and `try_from` still rejects empty vectors.
+        assert!(Vec1::<u8>::try_from(vec![]).is_err());
+    }
 }
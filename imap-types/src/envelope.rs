@@ -7,7 +7,7 @@ use bounded_static::ToStatic;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::core::NString;
+use crate::{core::NString, utils::escape_quoted};
 
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
@@ -53,3 +53,188 @@ pub struct Address<'a> {
     /// Host name
     pub host: NString<'a>,
 }
+
+impl<'a> Address<'a> {
+    /// Render the address as in an RFC 5322 header field, e.g., `Fred Foobar <foobar@example.com>`.
+    ///
+    /// The name, and the mailbox if it isn't a dot-atom, are quoted when required.
+    /// Non-UTF-8 bytes are replaced with `U+FFFD`.
+    ///
+    /// Addresses using group syntax render as the start (`Group:`) or the end (`;`) of a group.
+    /// Use [`Address::list_to_rfc5322`] to render complete groups.
+    pub fn to_rfc5322(&self) -> String {
+        let host = match self.host.as_bytes() {
+            Some(host) => String::from_utf8_lossy(host),
+            None => {
+                return match self.mailbox.as_bytes() {
+                    Some(group) => format!("{}:", phrase(group)),
+                    None => ";".into(),
+                };
+            }
+        };
+
+        let addr_spec = format!(
+            "{}@{host}",
+            local_part(self.mailbox.as_bytes().unwrap_or_default())
+        );
+
+        let route = match self.adl.as_bytes() {
+            Some(adl) if !adl.is_empty() => format!("{}:", String::from_utf8_lossy(adl)),
+            _ => String::new(),
+        };
+
+        match self.name.as_bytes() {
+            Some(name) if !name.is_empty() => format!("{} <{route}{addr_spec}>", phrase(name)),
+            _ if !route.is_empty() => format!("<{route}{addr_spec}>"),
+            _ => addr_spec,
+        }
+    }
+
+    /// Render a list of addresses as in an RFC 5322 header field, e.g., `a@example.com, Friends: b@example.com;`.
+    pub fn list_to_rfc5322(addresses: &[Address]) -> String {
+        let mut out = String::new();
+
+        for (index, address) in addresses.iter().enumerate() {
+            let is_group_end = address.host.is_nil() && address.mailbox.is_nil();
+
+            if let Some(previous) = index.checked_sub(1).map(|index| &addresses[index]) {
+                let previous_is_group_start = previous.host.is_nil() && !previous.mailbox.is_nil();
+
+                if previous_is_group_start && !is_group_end {
+                    out.push(' ');
+                } else if !previous_is_group_start && !is_group_end {
+                    out.push_str(", ");
+                }
+            }
+
+            out.push_str(&address.to_rfc5322());
+        }
+
+        out
+    }
+}
+
+/// `atext` as defined in RFC 5322.
+fn is_atext(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"!#$%&'*+-/=?^_`{|}~".contains(&byte)
+}
+
+/// Render a display name (or group name), quoting it when required.
+fn phrase(value: &[u8]) -> String {
+    let value = String::from_utf8_lossy(value);
+
+    let is_atoms = value
+        .split(' ')
+        .all(|word| !word.is_empty() && word.bytes().all(is_atext));
+
+    if is_atoms {
+        value.into_owned()
+    } else {
+        format!("\"{}\"", escape_quoted(&value))
+    }
+}
+
+/// Render a local-part, quoting it when it isn't a dot-atom.
+fn local_part(value: &[u8]) -> String {
+    let value = String::from_utf8_lossy(value);
+
+    let is_dot_atom = value
+        .split('.')
+        .all(|atom| !atom.is_empty() && atom.bytes().all(is_atext));
+
+    if is_dot_atom {
+        value.into_owned()
+    } else {
+        format!("\"{}\"", escape_quoted(&value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(
+        name: Option<&'static str>,
+        mailbox: Option<&'static str>,
+        host: Option<&'static str>,
+    ) -> Address<'static> {
+        let nstring = |value: Option<&'static str>| match value {
+            Some(value) => NString::try_from(value).unwrap(),
+            None => NString(None),
+        };
+
+        Address {
+            name: nstring(name),
+            adl: NString(None),
+            mailbox: nstring(mailbox),
+            host: nstring(host),
+        }
+    }
+
+    #[test]
+    fn test_address_to_rfc5322() {
+        let tests = [
+            (
+                address(Some("Fred Foobar"), Some("foobar"), Some("example.com")),
+                "Fred Foobar <foobar@example.com>",
+            ),
+            (
+                address(Some("Foobar, Fred"), Some("foobar"), Some("example.com")),
+                "\"Foobar, Fred\" <foobar@example.com>",
+            ),
+            (
+                address(Some("\"Fred\""), Some("fred"), Some("example.com")),
+                "\"\\\"Fred\\\"\" <fred@example.com>",
+            ),
+            (
+                address(None, Some("foobar"), Some("example.com")),
+                "foobar@example.com",
+            ),
+            (
+                address(Some(""), Some("john.doe"), Some("example.com")),
+                "john.doe@example.com",
+            ),
+            (
+                address(None, Some("john doe"), Some("example.com")),
+                "\"john doe\"@example.com",
+            ),
+            (address(None, Some("Friends"), None), "Friends:"),
+            (
+                address(None, Some("undisclosed recipients"), None),
+                "undisclosed recipients:",
+            ),
+            (address(None, None, None), ";"),
+        ];
+
+        for (test, expected) in tests {
+            assert_eq!(test.to_rfc5322(), expected);
+        }
+
+        let mut with_route = address(None, Some("foobar"), Some("example.com"));
+        with_route.adl = NString::try_from("@relay.example.com").unwrap();
+        assert_eq!(
+            with_route.to_rfc5322(),
+            "<@relay.example.com:foobar@example.com>"
+        );
+    }
+
+    #[test]
+    fn test_address_list_to_rfc5322() {
+        let addresses = [
+            address(Some("Alice"), Some("alice"), Some("example.com")),
+            address(None, Some("Friends"), None),
+            address(None, Some("bob"), Some("example.com")),
+            address(None, Some("carol"), Some("example.com")),
+            address(None, None, None),
+            address(None, Some("Empty"), None),
+            address(None, None, None),
+            address(None, Some("dave"), Some("example.com")),
+        ];
+
+        assert_eq!(
+            Address::list_to_rfc5322(&addresses),
+            "Alice <alice@example.com>, Friends: bob@example.com, carol@example.com;, Empty:;, dave@example.com"
+        );
+        assert_eq!(Address::list_to_rfc5322(&[]), "");
+    }
+}
@@ -3,16 +3,23 @@
 #[cfg(feature = "ext_binary")]
 pub mod binary;
 pub mod compress;
+pub mod condstore_qresync;
 pub mod enable;
+#[cfg(feature = "ext_gmail")]
+pub mod gmail;
 pub mod idle;
 #[cfg(feature = "ext_metadata")]
 pub mod metadata;
 pub mod r#move;
+#[cfg(feature = "ext_preview")]
+pub mod preview;
 pub mod quota;
+#[cfg(feature = "ext_replace")]
+pub mod replace;
 #[cfg(feature = "ext_sort_thread")]
 pub mod sort;
+pub mod special_use;
 #[cfg(feature = "ext_sort_thread")]
 pub mod thread;
-#[cfg(feature = "ext_uidplus")]
 pub mod uidplus;
 pub mod unselect;
@@ -9,6 +9,9 @@
 //! * the [`Capability`](crate::response::Capability) enum with a new variant [`Capability::Compress`](crate::response::Capability#variant.Compress),
 //! * the [`Command`](crate::command::Command) enum with a new variant [`Command::Compress`](crate::command::Command#variant.Compress), and
 //! * the [`Code`](crate::response::Code) enum with a new variant [`Code::CompressionActive`](crate::response::Code#variant.CompressionActive).
+//!
+//! Note: After a successful `COMPRESS`, i.e., directly after the CRLF of the tagged OK response, both directions are deflate-compressed.
+//! Bytes following this response must not be passed to a decoder without decompressing them first.
 
 use std::fmt::{Display, Formatter};
 
@@ -74,9 +77,10 @@ impl<'a> TryFrom<Atom<'a>> for CompressionAlgorithm {
     type Error = ValidationError;
 
     fn try_from(atom: Atom<'a>) -> Result<Self, Self::Error> {
-        match atom.as_ref().to_ascii_lowercase().as_ref() {
-            "deflate" => Ok(Self::Deflate),
-            _ => Err(ValidationError::new(ValidationErrorKind::Invalid)),
+        if atom.as_ref().eq_ignore_ascii_case("deflate") {
+            Ok(Self::Deflate)
+        } else {
+            Err(ValidationError::new(ValidationErrorKind::Invalid))
         }
     }
 }
@@ -0,0 +1,37 @@
+//! IMAP CONDSTORE and QRESYNC extensions (RFC 7162).
+//!
+//! [`SelectParameter`] is available without the `ext_condstore_qresync` feature, because
+//! [`CommandBody::Select`](crate::command::CommandBody::Select) and
+//! [`CommandBody::Examine`](crate::command::CommandBody::Examine) always carry parameters.
+
+use std::num::{NonZeroU32, NonZeroU64};
+
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "bounded-static")]
+use bounded_static::ToStatic;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::sequence::SequenceSet;
+
+/// Parameter of a `SELECT` or `EXAMINE` command.
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum SelectParameter {
+    /// Enable CONDSTORE for the selected mailbox.
+    CondStore,
+    /// Quickly resynchronize the mailbox.
+    QResync {
+        /// Last known UIDVALIDITY of the mailbox.
+        uid_validity: NonZeroU32,
+        /// Last known mod-sequence of the mailbox.
+        mod_sequence_value: NonZeroU64,
+        /// UIDs known to the client.
+        known_uids: Option<SequenceSet>,
+        /// Known message sequence numbers and their corresponding UIDs.
+        seq_match_data: Option<(SequenceSet, SequenceSet)>,
+    },
+}
@@ -0,0 +1,63 @@
+//! Gmail IMAP Extensions (`X-GM-EXT-1`)
+//!
+//! This extension defines a new type ...
+//!
+//! * [`GmailLabel`]
+//!
+//! ... and extends ...
+//!
+//! * the [`CommandBody`] enum with a new variant [`CommandBody::StoreGmailLabels`],
+//! * the [`MessageDataItemName`](crate::fetch::MessageDataItemName) enum with new variants `XGmMsgId`, `XGmThrId`, and `XGmLabels`, and
+//! * the [`MessageDataItem`](crate::fetch::MessageDataItem) enum with new variants `XGmMsgId`, `XGmThrId`, and `XGmLabels`.
+//!
+//! See <https://developers.google.com/gmail/imap/imap-extensions>.
+
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "bounded-static")]
+use bounded_static::ToStatic;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    command::CommandBody,
+    core::{AString, Atom},
+    flag::{StoreResponse, StoreType},
+    sequence::SequenceSet,
+};
+
+impl<'a> CommandBody<'a> {
+    /// Construct a STORE command that changes Gmail labels.
+    pub fn store_gmail_labels<S>(
+        sequence_set: S,
+        kind: StoreType,
+        response: StoreResponse,
+        labels: Vec<GmailLabel<'a>>,
+        uid: bool,
+    ) -> Result<Self, S::Error>
+    where
+        S: TryInto<SequenceSet>,
+    {
+        Ok(CommandBody::StoreGmailLabels {
+            sequence_set: sequence_set.try_into()?,
+            kind,
+            response,
+            labels,
+            uid,
+        })
+    }
+}
+
+/// Gmail label.
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GmailLabel<'a> {
+    /// System label, e.g., `\Inbox`, `\Sent`, or `\Important`.
+    ///
+    /// Note: The leading backslash is not part of the atom.
+    System(Atom<'a>),
+    /// User-defined label, e.g., `Work` or `"Muy Importante"`.
+    Label(AString<'a>),
+}
@@ -4,15 +4,18 @@
 //!
 //! *  [`CommandBody::idle()`](crate::command::CommandBody#method.idle)
 //!
-//! ... adds a new type ...
+//! ... adds new types ...
 //!
 //! * [`IdleDone`]
+//! * [`IdleTimer`] (and [`RECOMMENDED_IDLE_REFRESH`])
 //!
 //! ... and extends ...
 //!
 //! * [`CommandBody`](crate::command::CommandBody) enum with a new variant [`CommandBody::Idle`](crate::command::CommandBody#variant.Idle), and
 //! * [`Capability`](crate::response::Capability) enum with a new variant [`Capability::Idle`](crate::response::Capability#variant.Idle).
 
+use std::time::{Duration, Instant};
+
 #[cfg(feature = "arbitrary")]
 use arbitrary::Arbitrary;
 #[cfg(feature = "bounded-static")]
@@ -26,3 +29,124 @@ use serde::{Deserialize, Serialize};
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct IdleDone;
+
+/// Interval after which a client should re-issue IDLE.
+///
+/// Servers MAY log out clients after 30 minutes of inactivity. Thus, RFC 2177 recommends that
+/// clients terminate IDLE and re-issue it at least every 29 minutes.
+pub const RECOMMENDED_IDLE_REFRESH: Duration = Duration::from_secs(29 * 60);
+
+/// Tracks when IDLE was started to decide when it should be re-issued.
+///
+/// # Example
+///
+/// ```
+/// use imap_types::extensions::idle::IdleTimer;
+///
+/// let timer = IdleTimer::start();
+///
+/// // ... wait for server updates, but at most `timer.remaining()` ...
+///
+/// if timer.is_refresh_due() {
+///     // Send `DONE` and re-issue `IDLE`.
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IdleTimer {
+    started: Instant,
+    max_idle: Duration,
+}
+
+impl IdleTimer {
+    /// Start tracking now using [`RECOMMENDED_IDLE_REFRESH`].
+    pub fn start() -> Self {
+        Self::start_at(Instant::now())
+    }
+
+    /// Start tracking at `started` using [`RECOMMENDED_IDLE_REFRESH`].
+    pub fn start_at(started: Instant) -> Self {
+        Self {
+            started,
+            max_idle: RECOMMENDED_IDLE_REFRESH,
+        }
+    }
+
+    /// Use a different interval, e.g., when a server is known to time out earlier.
+    pub fn with_max_idle(mut self, max_idle: Duration) -> Self {
+        self.max_idle = max_idle;
+        self
+    }
+
+    pub fn started(&self) -> Instant {
+        self.started
+    }
+
+    pub fn max_idle(&self) -> Duration {
+        self.max_idle
+    }
+
+    /// Time since IDLE was started.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed_at(Instant::now())
+    }
+
+    /// Time since IDLE was started (as seen at `now`).
+    pub fn elapsed_at(&self, now: Instant) -> Duration {
+        now.saturating_duration_since(self.started)
+    }
+
+    /// Time until IDLE should be re-issued.
+    pub fn remaining(&self) -> Duration {
+        self.remaining_at(Instant::now())
+    }
+
+    /// Time until IDLE should be re-issued (as seen at `now`).
+    pub fn remaining_at(&self, now: Instant) -> Duration {
+        self.max_idle.saturating_sub(self.elapsed_at(now))
+    }
+
+    /// Check if IDLE should be re-issued.
+    pub fn is_refresh_due(&self) -> bool {
+        self.is_refresh_due_at(Instant::now())
+    }
+
+    /// Check if IDLE should be re-issued (as seen at `now`).
+    pub fn is_refresh_due_at(&self, now: Instant) -> bool {
+        self.elapsed_at(now) >= self.max_idle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idle_timer() {
+        let started = Instant::now();
+        let timer = IdleTimer::start_at(started);
+
+        assert_eq!(timer.elapsed_at(started), Duration::ZERO);
+        assert_eq!(timer.remaining_at(started), RECOMMENDED_IDLE_REFRESH);
+        assert!(!timer.is_refresh_due_at(started));
+
+        let now = started + Duration::from_secs(10 * 60);
+        assert_eq!(timer.elapsed_at(now), Duration::from_secs(10 * 60));
+        assert_eq!(timer.remaining_at(now), Duration::from_secs(19 * 60));
+        assert!(!timer.is_refresh_due_at(now));
+
+        let now = started + RECOMMENDED_IDLE_REFRESH;
+        assert_eq!(timer.remaining_at(now), Duration::ZERO);
+        assert!(timer.is_refresh_due_at(now));
+
+        let now = started + Duration::from_secs(60 * 60);
+        assert_eq!(timer.remaining_at(now), Duration::ZERO);
+        assert!(timer.is_refresh_due_at(now));
+
+        // A point in time before the start doesn't underflow.
+        let timer = IdleTimer::start_at(now);
+        assert_eq!(timer.elapsed_at(started), Duration::ZERO);
+
+        let timer = IdleTimer::start_at(started).with_max_idle(Duration::from_secs(60));
+        assert!(timer.is_refresh_due_at(started + Duration::from_secs(60)));
+    }
+}
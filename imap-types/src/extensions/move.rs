@@ -1,7 +1,14 @@
 //! IMAP - MOVE Extension
 
+use std::num::NonZeroU32;
+
+#[cfg(feature = "ext_uidplus")]
+use crate::response::Code;
 use crate::{
-    command::CommandBody, extensions::r#move::error::MoveError, mailbox::Mailbox,
+    command::CommandBody,
+    extensions::{r#move::error::MoveError, uidplus::UidSet},
+    mailbox::Mailbox,
+    response::{Data, Response},
     sequence::SequenceSet,
 };
 
@@ -23,6 +30,84 @@ impl<'a> CommandBody<'a> {
     }
 }
 
+/// Outcome of a `MOVE` command aggregated from the server's responses.
+///
+/// A server responds to `MOVE` with an `EXPUNGE` for every message removed from the source mailbox
+/// and, when UIDPLUS is supported, a `COPYUID` response code in an untagged or the tagged `OK` (RFC
+/// 6851, section 4.3).
+///
+/// # Example
+///
+/// ```text
+/// C: a UID MOVE 42:43 foo
+/// S: * OK [COPYUID 432432 42:43 1202:1203]
+/// S: * 22 EXPUNGE
+/// S: * 22 EXPUNGE
+/// S: a OK Done
+/// ```
+///
+/// ... results in `expunged == [22, 22]` and a mapping of `42 -> 1202` and `43 -> 1203`.
+///
+/// Note: `COPYUID` codes are only recognized with the `ext_uidplus` feature. Otherwise,
+/// `uid_validity` is `None` and `copy_uid` is empty.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MoveResult {
+    /// Sequence numbers of the expunged messages in the order they were received.
+    ///
+    /// Note: Every `EXPUNGE` decrements the sequence numbers of all following messages. Thus, the
+    /// same number may appear multiple times and must be applied in order.
+    pub expunged: Vec<NonZeroU32>,
+    /// UIDVALIDITY of the destination mailbox.
+    pub uid_validity: Option<NonZeroU32>,
+    /// Source and destination UID sets of every `COPYUID` code.
+    ///
+    /// The sets are kept as sent by the server. Use [`MoveResult::copy_uid_mapping`] to map UIDs.
+    pub copy_uid: Vec<(UidSet, UidSet)>,
+}
+
+impl MoveResult {
+    /// Map source UIDs to destination UIDs.
+    ///
+    /// The mapping is computed lazily, because a UID set may cover billions of UIDs, e.g.,
+    /// `1:4294967295`.
+    pub fn copy_uid_mapping(&self) -> impl Iterator<Item = (NonZeroU32, NonZeroU32)> + '_ {
+        self.copy_uid
+            .iter()
+            .flat_map(|(source, destination)| source.iter().zip(destination.iter()))
+    }
+
+    /// Aggregate the responses to a `MOVE` command.
+    ///
+    /// Responses unrelated to `MOVE` are ignored.
+    pub fn from_responses<'r, 'a: 'r, I>(responses: I) -> Self
+    where
+        I: IntoIterator<Item = &'r Response<'a>>,
+    {
+        let mut result = Self::default();
+
+        for response in responses {
+            match response {
+                Response::Data(Data::Expunge(seq)) => result.expunged.push(*seq),
+                #[cfg(feature = "ext_uidplus")]
+                Response::Status(status) => {
+                    if let Some(Code::CopyUid {
+                        uid_validity,
+                        source,
+                        destination,
+                    }) = status.code()
+                    {
+                        result.uid_validity = Some(*uid_validity);
+                        result.copy_uid.push((source.clone(), destination.clone()));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        result
+    }
+}
+
 /// Error-related types.
 pub mod error {
     use thiserror::Error;
@@ -51,6 +51,7 @@ use crate::{
     extensions::quota::error::{QuotaError, QuotaRootError, SetQuotaError},
     mailbox::Mailbox,
     response::Data,
+    utils::to_ascii_lowercase_in,
 };
 
 impl<'a> CommandBody<'a> {
@@ -181,6 +182,12 @@ pub enum Resource<'a> {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ResourceOther<'a>(Atom<'a>);
 
+impl AsRef<str> for ResourceOther<'_> {
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
 impl_try_from!(Atom<'a>, 'a, &'a [u8], Resource<'a>);
 impl_try_from!(Atom<'a>, 'a, Vec<u8>, Resource<'a>);
 impl_try_from!(Atom<'a>, 'a, &'a str, Resource<'a>);
@@ -189,11 +196,13 @@ impl_try_from!(Atom<'a>, 'a, Cow<'a, str>, Resource<'a>);
 
 impl<'a> From<Atom<'a>> for Resource<'a> {
     fn from(atom: Atom<'a>) -> Self {
-        match atom.inner().to_ascii_uppercase().as_ref() {
-            "STORAGE" => Resource::Storage,
-            "MESSAGE" => Resource::Message,
-            "MAILBOX" => Resource::Mailbox,
-            "ANNOTATION-STORAGE" => Resource::AnnotationStorage,
+        let mut buffer = [0u8; 18];
+
+        match to_ascii_lowercase_in(atom.inner(), &mut buffer).unwrap_or_default() {
+            "storage" => Resource::Storage,
+            "message" => Resource::Message,
+            "mailbox" => Resource::Mailbox,
+            "annotation-storage" => Resource::AnnotationStorage,
             _ => Resource::Other(ResourceOther(atom)),
         }
     }
@@ -0,0 +1,49 @@
+//! IMAP REPLACE Extension
+//!
+//! This extension extends ...
+//!
+//! * the [`Capability`](crate::response::Capability) enum with a new variant [`Capability::Replace`](crate::response::Capability#variant.Replace), and
+//! * the [`CommandBody`] enum with a new variant [`CommandBody::Replace`].
+//!
+//! A successful REPLACE responds like APPEND, e.g., with [`Code::AppendUid`](crate::response::Code#variant.AppendUid) when UIDPLUS is supported.
+
+#[cfg(feature = "ext_binary")]
+use crate::extensions::binary::LiteralOrLiteral8;
+use crate::{
+    command::{error::ReplaceError, CommandBody},
+    core::Literal,
+    datetime::DateTime,
+    flag::Flag,
+    mailbox::Mailbox,
+    sequence::SeqOrUid,
+};
+
+impl<'a> CommandBody<'a> {
+    /// Construct a REPLACE command.
+    #[allow(clippy::type_complexity)]
+    pub fn replace<S, M, D>(
+        sequence: S,
+        mailbox: M,
+        flags: Vec<Flag<'a>>,
+        date: Option<DateTime>,
+        message: D,
+        uid: bool,
+    ) -> Result<Self, ReplaceError<S::Error, M::Error, D::Error>>
+    where
+        S: TryInto<SeqOrUid>,
+        M: TryInto<Mailbox<'a>>,
+        D: TryInto<Literal<'a>>,
+    {
+        Ok(CommandBody::Replace {
+            sequence: sequence.try_into().map_err(ReplaceError::Sequence)?,
+            mailbox: mailbox.try_into().map_err(ReplaceError::Mailbox)?,
+            flags,
+            date,
+            #[cfg(not(feature = "ext_binary"))]
+            message: message.try_into().map_err(ReplaceError::Data)?,
+            #[cfg(feature = "ext_binary")]
+            message: LiteralOrLiteral8::Literal(message.try_into().map_err(ReplaceError::Data)?),
+            uid,
+        })
+    }
+}
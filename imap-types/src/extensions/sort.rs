@@ -21,9 +21,10 @@ pub enum SortAlgorithm<'a> {
 
 impl<'a> From<Atom<'a>> for SortAlgorithm<'a> {
     fn from(value: Atom<'a>) -> Self {
-        match value.as_ref().to_lowercase().as_ref() {
-            "display" => Self::Display,
-            _ => Self::Other(SortAlgorithmOther(value)),
+        if value.as_ref().eq_ignore_ascii_case("display") {
+            Self::Display
+        } else {
+            Self::Other(SortAlgorithmOther(value))
         }
     }
 }
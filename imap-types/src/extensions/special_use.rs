@@ -0,0 +1,25 @@
+//! IMAP LIST Extension for Special-Use Mailboxes (RFC 6154).
+//!
+//! [`CreateParameter`] is available without the `ext_special_use` feature, because
+//! [`CommandBody::Create`](crate::command::CommandBody::Create) always carries parameters.
+
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "bounded-static")]
+use bounded_static::ToStatic;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::flag::FlagNameAttribute;
+
+/// Parameter of a `CREATE` command.
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum CreateParameter<'a> {
+    /// Special-use attributes to assign to the new mailbox, e.g., `\Archive`.
+    ///
+    /// A server that can't assign the attributes fails with the `USEATTR` response code.
+    Use(Vec<FlagNameAttribute<'a>>),
+}
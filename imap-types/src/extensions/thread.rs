@@ -12,7 +12,10 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "arbitrary")]
 use crate::arbitrary::impl_arbitrary_try_from;
-use crate::core::{Atom, Vec1, Vec2};
+use crate::{
+    core::{Atom, Vec1, Vec2},
+    utils::to_ascii_lowercase_in,
+};
 
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -151,7 +154,9 @@ pub enum ThreadingAlgorithm<'a> {
 
 impl<'a> From<Atom<'a>> for ThreadingAlgorithm<'a> {
     fn from(value: Atom<'a>) -> Self {
-        match value.as_ref().to_lowercase().as_ref() {
+        let mut buffer = [0u8; 14];
+
+        match to_ascii_lowercase_in(value.as_ref(), &mut buffer).unwrap_or_default() {
             "orderedsubject" => Self::OrderedSubject,
             "references" => Self::References,
             _ => Self::Other(ThreadingAlgorithmOther(value)),
@@ -1,3 +1,8 @@
+//! The IMAP UIDPLUS Extension
+//!
+//! [`UidSet`] is available without the `ext_uidplus` feature, because
+//! [`MoveResult`](crate::extensions::r#move::MoveResult) always carries UID sets.
+
 use std::num::NonZeroU32;
 
 #[cfg(feature = "arbitrary")]
@@ -8,6 +13,8 @@ use bounded_static::ToStatic;
 use serde::{Deserialize, Serialize};
 
 use crate::core::Vec1;
+#[cfg(feature = "ext_uidplus")]
+use crate::response::Code;
 
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
@@ -15,6 +22,24 @@ use crate::core::Vec1;
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct UidSet(pub Vec1<UidElement>);
 
+impl UidSet {
+    /// Iterate over all UIDs in the set.
+    ///
+    /// Ranges are expanded in ascending order regardless of how they were written, i.e., `3:1`
+    /// yields `1, 2, 3`. Otherwise, the order of the set is preserved.
+    pub fn iter(&self) -> impl Iterator<Item = NonZeroU32> + '_ {
+        self.0.as_ref().iter().flat_map(|element| {
+            let (from, to) = match *element {
+                UidElement::Single(uid) => (uid, uid),
+                UidElement::Range(a, b) => (a.min(b), a.max(b)),
+            };
+
+            // `NonZeroU32::new` can't fail because `from` is non-zero.
+            (from.get()..=to.get()).filter_map(NonZeroU32::new)
+        })
+    }
+}
+
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -23,3 +48,68 @@ pub enum UidElement {
     Single(NonZeroU32),
     Range(NonZeroU32, NonZeroU32),
 }
+
+#[cfg(feature = "ext_uidplus")]
+impl<'a> Code<'a> {
+    /// Map source UIDs to destination UIDs as announced in `COPYUID`.
+    ///
+    /// The source and destination sets are positionally aligned (RFC 4315), i.e., the n-th UID
+    /// of the source set was copied to the n-th UID of the destination set. Returns `None` when
+    /// this is not a [`Code::CopyUid`].
+    pub fn copy_uid_mapping(&self) -> Option<impl Iterator<Item = (NonZeroU32, NonZeroU32)> + '_> {
+        match self {
+            Code::CopyUid {
+                source,
+                destination,
+                ..
+            } => Some(source.iter().zip(destination.iter())),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uid(value: u32) -> NonZeroU32 {
+        NonZeroU32::new(value).unwrap()
+    }
+
+    #[test]
+    fn test_uid_set_iter() {
+        let set = UidSet(
+            vec![
+                UidElement::Single(uid(7)),
+                UidElement::Range(uid(3), uid(1)),
+                UidElement::Range(uid(10), uid(10)),
+            ]
+            .try_into()
+            .unwrap(),
+        );
+
+        assert_eq!(
+            set.iter().map(NonZeroU32::get).collect::<Vec<_>>(),
+            [7, 1, 2, 3, 10]
+        );
+    }
+
+    #[cfg(feature = "ext_uidplus")]
+    #[test]
+    fn test_copy_uid_mapping() {
+        let code = Code::CopyUid {
+            uid_validity: uid(1),
+            source: UidSet(UidElement::Range(uid(1), uid(3)).into()),
+            destination: UidSet(UidElement::Range(uid(10), uid(12)).into()),
+        };
+
+        assert_eq!(
+            code.copy_uid_mapping()
+                .unwrap()
+                .map(|(from, to)| (from.get(), to.get()))
+                .collect::<Vec<_>>(),
+            [(1, 10), (2, 11), (3, 12)]
+        );
+        assert!(Code::Alert.copy_uid_mapping().is_none());
+    }
+}
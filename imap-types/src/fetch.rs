@@ -14,6 +14,8 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "ext_binary")]
 use crate::core::NString8;
+#[cfg(feature = "ext_gmail")]
+use crate::extensions::gmail::GmailLabel;
 use crate::{
     body::BodyStructure,
     core::{AString, NString, Vec1},
@@ -241,6 +243,42 @@ pub enum MessageDataItemName<'a> {
 
     #[cfg(feature = "ext_binary")]
     BinarySize { section: Vec<NonZeroU32> },
+
+    /// A short text snippet of the message, e.g., to be shown in a message list.
+    ///
+    /// ```imap
+    /// PREVIEW
+    /// PREVIEW (LAZY)
+    /// ```
+    ///
+    /// With `lazy`, the server returns `NIL` instead of generating a preview that is not
+    /// readily available.
+    #[cfg(feature = "ext_preview")]
+    Preview { lazy: bool },
+
+    /// Gmail message ID.
+    ///
+    /// ```imap
+    /// X-GM-MSGID
+    /// ```
+    #[cfg(feature = "ext_gmail")]
+    XGmMsgId,
+
+    /// Gmail thread ID.
+    ///
+    /// ```imap
+    /// X-GM-THRID
+    /// ```
+    #[cfg(feature = "ext_gmail")]
+    XGmThrId,
+
+    /// Gmail labels.
+    ///
+    /// ```imap
+    /// X-GM-LABELS
+    /// ```
+    #[cfg(feature = "ext_gmail")]
+    XGmLabels,
 }
 
 /// Message data item.
@@ -377,6 +415,181 @@ pub enum MessageDataItem<'a> {
 
     #[cfg(feature = "ext_binary")]
     BinarySize { section: Vec<NonZeroU32>, size: u32 },
+
+    /// A short text snippet of the message.
+    ///
+    /// `NIL` when the preview was requested lazily and is not available yet.
+    ///
+    /// ```imap
+    /// PREVIEW
+    /// ```
+    #[cfg(feature = "ext_preview")]
+    Preview(NString<'a>),
+
+    /// A unique (64-bit) ID of the message in Gmail.
+    ///
+    /// ```imap
+    /// X-GM-MSGID
+    /// ```
+    #[cfg(feature = "ext_gmail")]
+    XGmMsgId(u64),
+
+    /// A (64-bit) ID of the thread the message belongs to in Gmail.
+    ///
+    /// ```imap
+    /// X-GM-THRID
+    /// ```
+    #[cfg(feature = "ext_gmail")]
+    XGmThrId(u64),
+
+    /// Labels of the message in Gmail.
+    ///
+    /// ```imap
+    /// X-GM-LABELS
+    /// ```
+    #[cfg(feature = "ext_gmail")]
+    XGmLabels(Vec<GmailLabel<'a>>),
+}
+
+impl<'a> MessageDataItem<'a> {
+    /// Check if this message data item is the answer to the given message data item name.
+    ///
+    /// `BODY[<section>]<<origin>>` matches `BODY[<section>]<<partial>>` and `BODY.PEEK[<section>]<<partial>>`
+    /// when the sections are equal and the origin equals the start of the partial range.
+    pub fn answers(&self, name: &MessageDataItemName) -> bool {
+        match (self, name) {
+            (Self::Body(_), MessageDataItemName::Body)
+            | (Self::BodyStructure(_), MessageDataItemName::BodyStructure)
+            | (Self::Envelope(_), MessageDataItemName::Envelope)
+            | (Self::Flags(_), MessageDataItemName::Flags)
+            | (Self::InternalDate(_), MessageDataItemName::InternalDate)
+            | (Self::Rfc822(_), MessageDataItemName::Rfc822)
+            | (Self::Rfc822Header(_), MessageDataItemName::Rfc822Header)
+            | (Self::Rfc822Size(_), MessageDataItemName::Rfc822Size)
+            | (Self::Rfc822Text(_), MessageDataItemName::Rfc822Text)
+            | (Self::Uid(_), MessageDataItemName::Uid) => true,
+            #[cfg(feature = "ext_preview")]
+            (Self::Preview(_), MessageDataItemName::Preview { .. }) => true,
+            #[cfg(feature = "ext_gmail")]
+            (Self::XGmMsgId(_), MessageDataItemName::XGmMsgId)
+            | (Self::XGmThrId(_), MessageDataItemName::XGmThrId)
+            | (Self::XGmLabels(_), MessageDataItemName::XGmLabels) => true,
+            (
+                Self::BodyExt {
+                    section, origin, ..
+                },
+                MessageDataItemName::BodyExt {
+                    section: requested_section,
+                    partial,
+                    ..
+                },
+            ) => section == requested_section && *origin == partial.map(|(start, _)| start),
+            #[cfg(feature = "ext_binary")]
+            (
+                Self::Binary { section, .. },
+                MessageDataItemName::Binary {
+                    section: requested_section,
+                    ..
+                },
+            )
+            | (
+                Self::BinarySize { section, .. },
+                MessageDataItemName::BinarySize {
+                    section: requested_section,
+                },
+            ) => section == requested_section,
+            _ => false,
+        }
+    }
+}
+
+/// Return all message data items that don't answer any of the `requested` message data item names.
+///
+/// Servers may include message data items that were not requested, e.g., `FLAGS` after a flag
+/// change or `UID` in the response to `UID FETCH`. This is allowed but may be useful to detect in
+/// strict clients or test suites.
+///
+/// Note: Macros must be [expanded](Macro::expand) before they are passed as `requested`.
+pub fn unexpected_attributes<'a, 'b>(
+    requested: &[MessageDataItemName],
+    got: &'b [MessageDataItem<'a>],
+) -> Vec<&'b MessageDataItem<'a>> {
+    got.iter()
+        .filter(|item| !requested.iter().any(|name| item.answers(name)))
+        .collect()
+}
+
+/// Typed access to the message data items of a `FETCH` response.
+///
+/// All accessors return `None` when the message data item is absent.
+///
+/// # Duplicates
+///
+/// A message data item may occur more than once, e.g., when a (nonconforming) server sends `FLAGS`
+/// twice. The accessors return the *last* occurrence, because it reflects the most recent state of
+/// dynamic items such as `FLAGS`. Iterate over the items to see all of them.
+///
+/// Equivalent items are not merged, i.e., [`body_section(None)`](Self::body_section) returns
+/// `BODY[]` only and ignores `RFC822`.
+impl<'a> Vec1<MessageDataItem<'a>> {
+    /// Returns the `UID`.
+    pub fn uid(&self) -> Option<NonZeroU32> {
+        self.find_item(|item| match item {
+            MessageDataItem::Uid(uid) => Some(*uid),
+            _ => None,
+        })
+    }
+
+    /// Returns the `FLAGS`.
+    pub fn flags(&self) -> Option<&[FlagFetch<'a>]> {
+        self.find_item(|item| match item {
+            MessageDataItem::Flags(flags) => Some(flags.as_slice()),
+            _ => None,
+        })
+    }
+
+    /// Returns the `INTERNALDATE`.
+    pub fn internal_date(&self) -> Option<&DateTime> {
+        self.find_item(|item| match item {
+            MessageDataItem::InternalDate(date_time) => Some(date_time),
+            _ => None,
+        })
+    }
+
+    /// Returns the `ENVELOPE`.
+    pub fn envelope(&self) -> Option<&Envelope<'a>> {
+        self.find_item(|item| match item {
+            MessageDataItem::Envelope(envelope) => Some(envelope),
+            _ => None,
+        })
+    }
+
+    /// Returns the `RFC822.SIZE`.
+    pub fn size(&self) -> Option<u32> {
+        self.find_item(|item| match item {
+            MessageDataItem::Rfc822Size(size) => Some(*size),
+            _ => None,
+        })
+    }
+
+    /// Returns the data of `BODY[<section>]`, e.g., `None` for `BODY[]`.
+    ///
+    /// Note: Partial data, i.e., `BODY[<section>]<<origin>>`, is returned as well.
+    pub fn body_section(&self, section: Option<&Section>) -> Option<&NString<'a>> {
+        self.find_item(|item| match item {
+            MessageDataItem::BodyExt {
+                section: got, data, ..
+            } if got.as_ref() == section => Some(data),
+            _ => None,
+        })
+    }
+
+    fn find_item<'b, T, F>(&'b self, f: F) -> Option<T>
+    where
+        F: FnMut(&'b MessageDataItem<'a>) -> Option<T>,
+    {
+        self.0.iter().rev().find_map(f)
+    }
 }
 
 /// A part specifier is either a part number or one of the following:
@@ -480,3 +693,137 @@ pub enum PartSpecifier<'a> {
     Mime,
     Text,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flag::Flag;
+
+    #[test]
+    fn test_unexpected_attributes() {
+        let requested = [
+            MessageDataItemName::Rfc822Size,
+            MessageDataItemName::BodyExt {
+                section: Some(Section::Text(None)),
+                partial: Some((0, NonZeroU32::new(5).unwrap())),
+                peek: true,
+            },
+        ];
+
+        let body = MessageDataItem::BodyExt {
+            section: Some(Section::Text(None)),
+            origin: Some(0),
+            data: NString::try_from("Hello").unwrap(),
+        };
+        let flags = MessageDataItem::Flags(vec![FlagFetch::Flag(Flag::Seen)]);
+        let got = [
+            MessageDataItem::Rfc822Size(1337),
+            body.clone(),
+            flags.clone(),
+        ];
+
+        assert_eq!(unexpected_attributes(&requested, &got), [&flags]);
+        assert_eq!(
+            unexpected_attributes(&requested, &got[..2]),
+            Vec::<&MessageDataItem>::new()
+        );
+
+        // Section and origin must match.
+        let other = [MessageDataItemName::BodyExt {
+            section: Some(Section::Text(None)),
+            partial: None,
+            peek: false,
+        }];
+        assert_eq!(
+            unexpected_attributes(&other, std::slice::from_ref(&body)),
+            [&body]
+        );
+
+        let expanded = Macro::Fast.expand();
+        assert!(unexpected_attributes(&expanded, &[flags]).is_empty());
+    }
+
+    #[test]
+    fn test_message_data_item_accessors() {
+        let date_time = DateTime::try_from(
+            chrono::DateTime::parse_from_rfc3339("1996-07-17T02:44:25-07:00").unwrap(),
+        )
+        .unwrap();
+        let envelope = Envelope {
+            date: NString(None),
+            subject: NString::try_from("Hi").unwrap(),
+            from: vec![],
+            sender: vec![],
+            reply_to: vec![],
+            to: vec![],
+            cc: vec![],
+            bcc: vec![],
+            in_reply_to: NString(None),
+            message_id: NString(None),
+        };
+        let items = Vec1::try_from(vec![
+            MessageDataItem::Uid(NonZeroU32::new(5).unwrap()),
+            MessageDataItem::Flags(vec![FlagFetch::Flag(Flag::Seen)]),
+            MessageDataItem::InternalDate(date_time.clone()),
+            MessageDataItem::Rfc822Size(200),
+            MessageDataItem::Envelope(envelope.clone()),
+            MessageDataItem::BodyExt {
+                section: None,
+                origin: None,
+                data: NString::try_from("Hello").unwrap(),
+            },
+            MessageDataItem::BodyExt {
+                section: Some(Section::Header(None)),
+                origin: None,
+                data: NString(None),
+            },
+        ])
+        .unwrap();
+
+        assert_eq!(items.uid(), NonZeroU32::new(5));
+        assert_eq!(items.flags(), Some([FlagFetch::Flag(Flag::Seen)].as_ref()));
+        assert_eq!(items.internal_date(), Some(&date_time));
+        assert_eq!(items.envelope(), Some(&envelope));
+        assert_eq!(items.size(), Some(200));
+        assert_eq!(
+            items.body_section(None),
+            Some(&NString::try_from("Hello").unwrap())
+        );
+        assert_eq!(
+            items.body_section(Some(&Section::Header(None))),
+            Some(&NString(None))
+        );
+        assert_eq!(items.body_section(Some(&Section::Text(None))), None);
+
+        let items = Vec1::from(MessageDataItem::Rfc822Size(1));
+        assert_eq!(items.size(), Some(1));
+        assert_eq!(items.uid(), None);
+        assert_eq!(items.flags(), None);
+        assert_eq!(items.internal_date(), None);
+        assert_eq!(items.envelope(), None);
+        assert_eq!(items.body_section(None), None);
+    }
+
+    #[test]
+    fn test_message_data_item_accessors_duplicates() {
+        let items = Vec1::try_from(vec![
+            MessageDataItem::Flags(vec![FlagFetch::Flag(Flag::Seen)]),
+            MessageDataItem::Uid(NonZeroU32::new(5).unwrap()),
+            MessageDataItem::Rfc822(NString::try_from("Hello").unwrap()),
+            MessageDataItem::Flags(vec![
+                FlagFetch::Flag(Flag::Seen),
+                FlagFetch::Flag(Flag::Deleted),
+            ]),
+        ])
+        .unwrap();
+
+        // Last wins.
+        assert_eq!(
+            items.flags(),
+            Some([FlagFetch::Flag(Flag::Seen), FlagFetch::Flag(Flag::Deleted)].as_ref())
+        );
+        assert_eq!(items.uid(), NonZeroU32::new(5));
+        // `RFC822` is not `BODY[]`.
+        assert_eq!(items.body_section(None), None);
+    }
+}
@@ -1,6 +1,10 @@
 //! Flag-related types.
 
-use std::fmt::{Display, Formatter};
+use std::{
+    cmp::Ordering,
+    fmt::{Display, Formatter},
+    hash::{Hash, Hasher},
+};
 
 #[cfg(feature = "arbitrary")]
 use arbitrary::Arbitrary;
@@ -9,7 +13,7 @@ use bounded_static::ToStatic;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::{core::Atom, error::ValidationError};
+use crate::{core::Atom, error::ValidationError, utils::to_ascii_lowercase_in};
 
 /// There are two types of flags in IMAP4rev1: System and keyword flags.
 ///
@@ -53,7 +57,9 @@ pub struct FlagExtension<'a>(Atom<'a>);
 
 impl<'a> Flag<'a> {
     pub fn system(atom: Atom<'a>) -> Self {
-        match atom.as_ref().to_ascii_lowercase().as_ref() {
+        let mut buffer = [0u8; 8];
+
+        match to_ascii_lowercase_in(atom.as_ref(), &mut buffer).unwrap_or_default() {
             "answered" => Self::Answered,
             "deleted" => Self::Deleted,
             "draft" => Self::Draft,
@@ -94,6 +100,168 @@ impl<'a> Display for Flag<'a> {
     }
 }
 
+/// A set of flags with order-independent equality.
+///
+/// Flags are deduplicated and kept in a canonical order. Thus, `(\Seen \Flagged)` and
+/// `(\Flagged \Seen)` compare equal, and encoding a set is deterministic. System flags (including
+/// extension flags such as `\Foo`) are compared case-insensitively, keywords case-sensitively.
+///
+/// # Example
+///
+/// ```
+/// use imap_types::flag::{Flag, FlagSet};
+///
+/// let a = FlagSet::new([Flag::Seen, Flag::Flagged]);
+/// let b = FlagSet::new([Flag::Flagged, Flag::Seen, Flag::Seen]);
+///
+/// assert_eq!(a, b);
+/// assert_eq!(b.as_ref(), [Flag::Flagged, Flag::Seen]);
+/// ```
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[derive(Debug, Clone, Default)]
+pub struct FlagSet<'a>(Vec<Flag<'a>>);
+
+impl<'a> FlagSet<'a> {
+    /// Create a set of flags, dropping duplicates.
+    pub fn new<I>(flags: I) -> Self
+    where
+        I: IntoIterator<Item = Flag<'a>>,
+    {
+        let mut flags: Vec<_> = flags.into_iter().collect();
+        flags.sort_by(canonical_cmp);
+        flags.dedup_by(|a, b| canonical_cmp(a, b) == Ordering::Equal);
+
+        Self(flags)
+    }
+
+    /// Insert a flag. Returns `false` when the flag was already contained.
+    pub fn insert(&mut self, flag: Flag<'a>) -> bool {
+        match self.0.binary_search_by(|known| canonical_cmp(known, &flag)) {
+            Ok(_) => false,
+            Err(index) => {
+                self.0.insert(index, flag);
+                true
+            }
+        }
+    }
+
+    pub fn contains(&self, flag: &Flag) -> bool {
+        self.0
+            .binary_search_by(|known| canonical_cmp(known, flag))
+            .is_ok()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Flag<'a>> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn into_inner(self) -> Vec<Flag<'a>> {
+        self.0
+    }
+}
+
+impl<'a> AsRef<[Flag<'a>]> for FlagSet<'a> {
+    fn as_ref(&self) -> &[Flag<'a>] {
+        &self.0
+    }
+}
+
+impl<'a> FromIterator<Flag<'a>> for FlagSet<'a> {
+    fn from_iter<I: IntoIterator<Item = Flag<'a>>>(iter: I) -> Self {
+        Self::new(iter)
+    }
+}
+
+impl<'a> IntoIterator for FlagSet<'a> {
+    type Item = Flag<'a>;
+    type IntoIter = std::vec::IntoIter<Flag<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> PartialEq for FlagSet<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a> Eq for FlagSet<'a> {}
+
+impl<'a> PartialOrd for FlagSet<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Ord for FlagSet<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .map(|(a, b)| canonical_cmp(a, b))
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or_else(|| self.0.len().cmp(&other.0.len()))
+    }
+}
+
+impl<'a> Hash for FlagSet<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Must be consistent with `canonical_cmp`.
+        state.write_usize(self.0.len());
+
+        for flag in &self.0 {
+            let (rank, name) = canonical_key(flag);
+            state.write_u8(rank);
+
+            match flag {
+                Flag::Extension(_) => {
+                    name.bytes()
+                        .for_each(|byte| state.write_u8(byte.to_ascii_lowercase()));
+                }
+                _ => state.write(name.as_bytes()),
+            }
+
+            state.write_u8(0xff);
+        }
+    }
+}
+
+fn canonical_key<'f>(flag: &'f Flag) -> (u8, &'f str) {
+    match flag {
+        Flag::Answered => (0, ""),
+        Flag::Deleted => (1, ""),
+        Flag::Draft => (2, ""),
+        Flag::Flagged => (3, ""),
+        Flag::Seen => (4, ""),
+        Flag::Extension(FlagExtension(atom)) => (5, atom.as_ref()),
+        Flag::Keyword(atom) => (6, atom.as_ref()),
+    }
+}
+
+/// Compare flags case-insensitively for system flags and case-sensitively for keywords.
+pub(crate) fn canonical_cmp(a: &Flag, b: &Flag) -> Ordering {
+    let (rank_a, name_a) = canonical_key(a);
+    let (rank_b, name_b) = canonical_key(b);
+
+    rank_a.cmp(&rank_b).then_with(|| match a {
+        Flag::Extension(_) => name_a
+            .bytes()
+            .map(|byte| byte.to_ascii_lowercase())
+            .cmp(name_b.bytes().map(|byte| byte.to_ascii_lowercase())),
+        _ => name_a.cmp(name_b),
+    })
+}
+
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -165,7 +333,9 @@ impl<'a> FlagNameAttribute<'a> {
 
 impl<'a> From<Atom<'a>> for FlagNameAttribute<'a> {
     fn from(atom: Atom<'a>) -> Self {
-        match atom.as_ref().to_ascii_lowercase().as_ref() {
+        let mut buffer = [0u8; 11];
+
+        match to_ascii_lowercase_in(atom.as_ref(), &mut buffer).unwrap_or_default() {
             "noinferiors" => Self::Noinferiors,
             "noselect" => Self::Noselect,
             "marked" => Self::Marked,
@@ -205,3 +375,64 @@ pub enum StoreResponse {
     Answer,
     Silent,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn flags(flags: &[&'static str]) -> FlagSet<'static> {
+        flags
+            .iter()
+            .map(|flag| Flag::try_from(*flag).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_flag_set() {
+        assert_eq!(
+            flags(&["\\Seen", "\\Flagged"]),
+            flags(&["\\Flagged", "\\Seen"])
+        );
+        assert_eq!(
+            flags(&["\\Seen", "\\Flagged", "\\seen"]),
+            flags(&["\\Flagged", "\\Seen"])
+        );
+        assert_eq!(flags(&["\\Foo", "\\fOO"]), flags(&["\\foo"]));
+        assert_eq!(flags(&["\\Foo", "\\fOO"]).len(), 1);
+        assert_ne!(flags(&["Junk"]), flags(&["junk"]));
+        assert_eq!(flags(&["Junk", "junk", "Junk"]).len(), 2);
+        assert_ne!(flags(&["\\Seen"]), flags(&["\\Seen", "\\Flagged"]));
+
+        // Canonical order.
+        assert_eq!(
+            flags(&["Junk", "\\Foo", "\\Seen", "$Label", "\\Answered"]).into_inner(),
+            [
+                Flag::Answered,
+                Flag::Seen,
+                Flag::try_from("\\Foo").unwrap(),
+                Flag::try_from("$Label").unwrap(),
+                Flag::try_from("Junk").unwrap(),
+            ]
+        );
+
+        // `Hash` is consistent with `Eq`.
+        let set: HashSet<_> = [flags(&["\\Foo", "\\Seen"]), flags(&["\\seen", "\\FOO"])]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_flag_set_insert() {
+        let mut set = FlagSet::default();
+
+        assert!(set.insert(Flag::Seen));
+        assert!(set.insert(Flag::Answered));
+        assert!(!set.insert(Flag::try_from("\\SEEN").unwrap()));
+        assert!(set.contains(&Flag::Seen));
+        assert!(!set.contains(&Flag::Deleted));
+        assert_eq!(set.as_ref(), [Flag::Answered, Flag::Seen]);
+    }
+}
@@ -123,6 +123,10 @@
 //! |ext_mailbox_referrals|IMAP4 Mailbox Referrals ([RFC 2193])                                                   |Unfinished|
 //! |ext_binary           |IMAP4 Binary Content Extension ([RFC 3516])                                            |Unfinished|
 //! |ext_metadata         |The IMAP METADATA Extension ([RFC 5464])                                               |Unfinished|
+//! |ext_preview          |IMAP Extension for Returning Preview Text ([RFC 8970])                                 |Unfinished|
+//! |ext_replace          |IMAP REPLACE Extension ([RFC 8508])                                                    |Unfinished|
+//! |ext_gmail            |Gmail IMAP Extensions ([X-GM-EXT-1])                                                   |Unfinished|
+//! |ext_special_use      |IMAP LIST Extension for Special-Use Mailboxes ([RFC 6154])                             |Unfinished|
 //! |starttls             |IMAP4rev1 ([RFC 3501]; section 6.2.1)                                                  |          |
 //!
 //! STARTTLS is not an IMAP extension but feature-gated because it [should be avoided](https://nostarttls.secvuln.info/).
@@ -161,10 +165,14 @@
 //! [RFC 5256]: https://datatracker.ietf.org/doc/html/rfc5256
 //! [RFC 5464]: https://datatracker.ietf.org/doc/html/rfc5464
 //! [RFC 5957]: https://datatracker.ietf.org/doc/html/rfc5957
+//! [RFC 6154]: https://datatracker.ietf.org/doc/html/rfc6154
 //! [RFC 6851]: https://datatracker.ietf.org/doc/html/rfc6851
 //! [RFC 7162]: https://datatracker.ietf.org/doc/html/rfc7162
 //! [RFC 7888]: https://datatracker.ietf.org/doc/html/rfc7888
+//! [RFC 8508]: https://datatracker.ietf.org/doc/html/rfc8508
+//! [RFC 8970]: https://datatracker.ietf.org/doc/html/rfc8970
 //! [RFC 9208]: https://datatracker.ietf.org/doc/html/rfc9208
+//! [X-GM-EXT-1]: https://developers.google.com/gmail/imap/imap-extensions
 
 #![forbid(unsafe_code)]
 #![deny(missing_debug_implementations)]
@@ -181,6 +189,7 @@ pub struct ReadmeDoctests;
 mod arbitrary;
 pub mod auth;
 pub mod body;
+pub mod casing;
 pub mod command;
 pub mod core;
 pub mod datetime;
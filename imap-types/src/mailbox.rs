@@ -1,18 +1,24 @@
 //! Mailbox-related types.
 
-use std::{borrow::Cow, str::from_utf8};
+use std::borrow::Cow;
 
 #[cfg(feature = "arbitrary")]
 use arbitrary::Arbitrary;
+use base64::{
+    alphabet,
+    engine::{general_purpose::NO_PAD, GeneralPurpose},
+    Engine,
+};
 #[cfg(feature = "bounded-static")]
 use bounded_static::ToStatic;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    core::{impl_try_from, AString, IString},
+    casing::is_inbox,
+    core::{impl_try_from, AString, IString, QuotedChar},
     error::{ValidationError, ValidationErrorKind},
-    mailbox::error::MailboxOtherError,
+    mailbox::error::{MailboxOtherError, MailboxPathError},
     utils::indicators::is_list_char,
 };
 
@@ -188,9 +194,10 @@ impl_try_from!(AString<'a>, 'a, String, Mailbox<'a>);
 
 impl<'a> From<AString<'a>> for Mailbox<'a> {
     fn from(value: AString<'a>) -> Self {
-        match from_utf8(value.as_ref()) {
-            Ok(value) if value.to_ascii_lowercase() == "inbox" => Self::Inbox,
-            _ => Self::Other(MailboxOther::try_from(value).unwrap()),
+        if is_inbox(value.as_ref()) {
+            Self::Inbox
+        } else {
+            Self::Other(MailboxOther::try_from(value).unwrap())
         }
     }
 }
@@ -205,7 +212,7 @@ pub struct MailboxOther<'a>(pub(crate) AString<'a>);
 
 impl<'a> MailboxOther<'a> {
     pub fn validate(value: impl AsRef<[u8]>) -> Result<(), MailboxOtherError> {
-        if value.as_ref().to_ascii_lowercase() == b"inbox" {
+        if is_inbox(value.as_ref()) {
             return Err(MailboxOtherError::Reserved);
         }
 
@@ -272,6 +279,202 @@ impl<'a> AsRef<[u8]> for MailboxOther<'a> {
     }
 }
 
+/// Hierarchical mailbox name split into (human-readable) segments.
+///
+/// Servers use a hierarchy delimiter (see [`Data::hierarchy_delimiter`](crate::response::Data::hierarchy_delimiter))
+/// to separate levels of hierarchy, e.g., `Archive/2023` or `Archive.2023`. Also, non-ASCII names
+/// are represented using "modified UTF-7" (see RFC 3501, section 5.1.3).
+/// This type takes care of both, so that clients don't need to hardcode a delimiter.
+///
+/// ```rust
+/// use imap_types::{core::QuotedChar, mailbox::MailboxPath};
+///
+/// let mut path = MailboxPath::new(QuotedChar::try_from('.').unwrap());
+/// path.push("Archive").unwrap();
+/// path.push("Entwürfe").unwrap();
+///
+/// let mailbox = path.to_mailbox();
+/// let path = MailboxPath::from_mailbox(&mailbox, QuotedChar::try_from('.').unwrap()).unwrap();
+/// assert_eq!(path.segments(), ["Archive", "Entwürfe"]);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MailboxPath {
+    delimiter: QuotedChar,
+    segments: Vec<String>,
+}
+
+impl MailboxPath {
+    /// Create an empty path.
+    pub fn new(delimiter: QuotedChar) -> Self {
+        Self {
+            delimiter,
+            segments: Vec::new(),
+        }
+    }
+
+    /// Create a path from segments.
+    pub fn from_segments<I, S>(delimiter: QuotedChar, segments: I) -> Result<Self, MailboxPathError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut path = Self::new(delimiter);
+
+        for segment in segments {
+            path.push(segment)?;
+        }
+
+        Ok(path)
+    }
+
+    /// Split a mailbox into segments.
+    pub fn from_mailbox(
+        mailbox: &Mailbox,
+        delimiter: QuotedChar,
+    ) -> Result<Self, MailboxPathError> {
+        let decoded = match mailbox {
+            Mailbox::Inbox => String::from("INBOX"),
+            Mailbox::Other(other) => decode_modified_utf7(other.as_ref())?,
+        };
+
+        Ok(Self {
+            delimiter,
+            segments: decoded
+                .split(delimiter.inner())
+                .map(ToOwned::to_owned)
+                .collect(),
+        })
+    }
+
+    /// Append a segment.
+    ///
+    /// Fails when the segment contains the hierarchy delimiter.
+    pub fn push<S>(&mut self, segment: S) -> Result<(), MailboxPathError>
+    where
+        S: Into<String>,
+    {
+        let segment = segment.into();
+
+        if segment.contains(self.delimiter.inner()) {
+            return Err(MailboxPathError::ContainsDelimiter);
+        }
+
+        self.segments.push(segment);
+
+        Ok(())
+    }
+
+    /// Remove the last segment.
+    pub fn pop(&mut self) -> Option<String> {
+        self.segments.pop()
+    }
+
+    pub fn delimiter(&self) -> QuotedChar {
+        self.delimiter
+    }
+
+    pub fn segments(&self) -> &[String] {
+        &self.segments
+    }
+
+    /// Join the segments and encode the result as modified UTF-7.
+    pub fn to_mailbox(&self) -> Mailbox<'static> {
+        let mut encoded = String::new();
+
+        for (index, segment) in self.segments.iter().enumerate() {
+            if index > 0 {
+                encoded.push(self.delimiter.inner());
+            }
+
+            encode_modified_utf7(segment, &mut encoded);
+        }
+
+        // # Safety
+        //
+        // `unwrap` is safe because modified UTF-7 is printable ASCII only and the delimiter is a
+        // `QuotedChar`, i.e., the result is always a valid quoted string.
+        Mailbox::try_from(encoded).unwrap()
+    }
+}
+
+const MODIFIED_BASE64: GeneralPurpose = GeneralPurpose::new(&alphabet::IMAP_MUTF7, NO_PAD);
+
+fn encode_modified_utf7(input: &str, output: &mut String) {
+    fn flush(utf16: &mut Vec<u16>, output: &mut String) {
+        if !utf16.is_empty() {
+            let bytes: Vec<u8> = utf16.iter().flat_map(|unit| unit.to_be_bytes()).collect();
+            output.push('&');
+            output.push_str(&MODIFIED_BASE64.encode(bytes));
+            output.push('-');
+            utf16.clear();
+        }
+    }
+
+    let mut utf16 = Vec::new();
+
+    for c in input.chars() {
+        match c {
+            '&' => {
+                flush(&mut utf16, output);
+                output.push_str("&-");
+            }
+            ' '..='~' => {
+                flush(&mut utf16, output);
+                output.push(c);
+            }
+            _ => utf16.extend_from_slice(c.encode_utf16(&mut [0; 2])),
+        }
+    }
+
+    flush(&mut utf16, output);
+}
+
+fn decode_modified_utf7(mut input: &[u8]) -> Result<String, MailboxPathError> {
+    let mut output = String::new();
+
+    while let Some((&byte, remaining)) = input.split_first() {
+        match byte {
+            b'&' => {
+                let end = remaining
+                    .iter()
+                    .position(|b| *b == b'-')
+                    .ok_or(MailboxPathError::InvalidUtf7)?;
+
+                if end == 0 {
+                    output.push('&');
+                } else {
+                    let bytes = MODIFIED_BASE64
+                        .decode(&remaining[..end])
+                        .map_err(|_| MailboxPathError::InvalidUtf7)?;
+
+                    if bytes.len() % 2 != 0 {
+                        return Err(MailboxPathError::InvalidUtf7);
+                    }
+
+                    let utf16: Vec<u16> = bytes
+                        .chunks_exact(2)
+                        .map(|unit| u16::from_be_bytes([unit[0], unit[1]]))
+                        .collect();
+
+                    output.push_str(
+                        &String::from_utf16(&utf16).map_err(|_| MailboxPathError::InvalidUtf7)?,
+                    );
+                }
+
+                input = &remaining[end + 1..];
+            }
+            b' '..=b'~' => {
+                output.push(byte as char);
+                input = remaining;
+            }
+            _ => return Err(MailboxPathError::InvalidUtf7),
+        }
+    }
+
+    Ok(output)
+}
+
 /// Error-related types.
 pub mod error {
     use thiserror::Error;
@@ -285,6 +488,14 @@ pub mod error {
         #[error("Reserved: Please use one of the typed variants")]
         Reserved,
     }
+
+    #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum MailboxPathError {
+        #[error("Segment must not contain the hierarchy delimiter")]
+        ContainsDelimiter,
+        #[error("Invalid modified UTF-7")]
+        InvalidUtf7,
+    }
 }
 
 #[cfg(test)]
@@ -320,6 +531,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_mailbox_path_join() {
+        let tests = [
+            ('.', vec!["Archive", "2023"], "Archive.2023"),
+            ('/', vec!["Archive", "2023"], "Archive/2023"),
+            ('/', vec!["INBOX"], "INBOX"),
+            ('/', vec!["Entwürfe"], "Entw&APw-rfe"),
+            ('/', vec!["Tom & Jerry"], "Tom &- Jerry"),
+            (
+                '/',
+                vec!["~peter", "mail", "台北", "日本語"],
+                "~peter/mail/&U,BTFw-/&ZeVnLIqe-",
+            ),
+        ];
+
+        for (delimiter, segments, expected) in tests {
+            let delimiter = QuotedChar::try_from(delimiter).unwrap();
+            let path = MailboxPath::from_segments(delimiter, segments.clone()).unwrap();
+
+            let mailbox = path.to_mailbox();
+            assert_eq!(mailbox, Mailbox::try_from(expected).unwrap());
+
+            let path = MailboxPath::from_mailbox(&mailbox, delimiter).unwrap();
+            assert_eq!(path.segments(), segments);
+        }
+    }
+
+    #[test]
+    fn test_mailbox_path_failing() {
+        let mut path = MailboxPath::new(QuotedChar::try_from('.').unwrap());
+        assert_eq!(path.push("a.b"), Err(MailboxPathError::ContainsDelimiter));
+        assert!(path.segments().is_empty());
+
+        let tests = [
+            b"&".as_ref(),
+            b"&Jjo",
+            b"&Jjo!-",
+            b"&AGE-\xff",
+            // Unpaired surrogate.
+            b"&2D0-",
+        ];
+
+        for test in tests {
+            let mailbox = Mailbox::try_from(test).unwrap();
+            assert_eq!(
+                MailboxPath::from_mailbox(&mailbox, QuotedChar::try_from('/').unwrap()),
+                Err(MailboxPathError::InvalidUtf7),
+            );
+        }
+    }
+
     #[test]
     fn test_conversion_mailbox_failing() {
         let tests = ["\x00", "A\x00", "\x00A"];
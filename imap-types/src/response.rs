@@ -3,7 +3,7 @@
 use std::{
     borrow::Cow,
     fmt::{Debug, Display, Formatter},
-    num::{NonZeroU32, TryFromIntError},
+    num::{NonZeroU32, NonZeroU64, TryFromIntError},
 };
 
 #[cfg(feature = "arbitrary")]
@@ -26,6 +26,7 @@ use crate::extensions::thread::{Thread, ThreadingAlgorithm};
 use crate::extensions::uidplus::UidSet;
 use crate::{
     auth::AuthMechanism,
+    casing::eq_capability,
     core::{impl_try_from, AString, Atom, Charset, QuotedChar, Tag, Text, Vec1},
     error::ValidationError,
     extensions::{
@@ -34,10 +35,11 @@ use crate::{
         quota::{QuotaGet, Resource},
     },
     fetch::MessageDataItem,
-    flag::{Flag, FlagNameAttribute, FlagPerm},
+    flag::{Flag, FlagFetch, FlagNameAttribute, FlagPerm},
     mailbox::Mailbox,
     response::error::{ContinueError, FetchError},
     status::StatusDataItem,
+    utils::to_ascii_lowercase_in,
 };
 
 /// Greeting.
@@ -91,6 +93,80 @@ impl<'a> Greeting<'a> {
     }
 }
 
+/// Builder for [`Greeting`]s.
+///
+/// ```rust
+/// use imap_types::{
+///     core::Vec1,
+///     response::{Capability, Code, Greeting, GreetingBuilder},
+/// };
+///
+/// let greeting = GreetingBuilder::ok()
+///     .capabilities(Vec1::from(Capability::Imap4Rev1))
+///     .text("Ready.")
+///     .unwrap();
+///
+/// assert_eq!(
+///     greeting,
+///     Greeting::ok(
+///         Some(Code::Capability(Vec1::from(Capability::Imap4Rev1))),
+///         "Ready."
+///     )
+///     .unwrap()
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GreetingBuilder<'a> {
+    kind: GreetingKind,
+    code: Option<Code<'a>>,
+}
+
+impl<'a> GreetingBuilder<'a> {
+    /// Start an OK greeting, i.e., the connection is in not authenticated state.
+    pub fn ok() -> Self {
+        Self::new(GreetingKind::Ok)
+    }
+
+    /// Start a PREAUTH greeting, i.e., the connection is already authenticated.
+    pub fn preauth() -> Self {
+        Self::new(GreetingKind::PreAuth)
+    }
+
+    /// Start a BYE greeting, i.e., the server rejects the connection.
+    pub fn bye() -> Self {
+        Self::new(GreetingKind::Bye)
+    }
+
+    fn new(kind: GreetingKind) -> Self {
+        Self { kind, code: None }
+    }
+
+    /// Set the response code.
+    pub fn code(mut self, code: Code<'a>) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Advertise `capabilities`, saving the client a CAPABILITY command.
+    ///
+    /// This replaces a previously set response code.
+    pub fn capabilities(self, capabilities: Vec1<Capability<'a>>) -> Self {
+        self.code(Code::Capability(capabilities))
+    }
+
+    /// Finish the greeting with a human-readable `text`.
+    pub fn text<T>(self, text: T) -> Result<Greeting<'a>, T::Error>
+    where
+        T: TryInto<Text<'a>>,
+    {
+        Ok(Greeting {
+            kind: self.kind,
+            code: self.code,
+            text: text.try_into()?,
+        })
+    }
+}
+
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -133,6 +209,18 @@ pub enum Response<'a> {
     Status(Status<'a>),
 }
 
+impl<'a> Response<'a> {
+    /// Get the tag of a tagged status response, i.e., the tag of the command this response completes.
+    ///
+    /// Returns `None` for untagged responses and command continuation requests.
+    pub fn tag(&self) -> Option<&Tag> {
+        match self {
+            Self::Status(status) => status.tag(),
+            Self::CommandContinuationRequest(_) | Self::Data(_) => None,
+        }
+    }
+}
+
 /// Status response.
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -218,6 +306,9 @@ pub struct Tagged<'a> {
 /// continue to read response data from the server until the
 /// connection is closed; this will ensure that any pending untagged
 /// or completion responses are read and processed.
+///
+/// Note: During LOGOUT, the BYE is followed by the tagged OK. Afterwards, the client should close
+/// the connection, too.
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -291,7 +382,7 @@ impl<'a> Status<'a> {
         }
     }
 
-    pub fn code(&self) -> Option<&Code> {
+    pub fn code(&self) -> Option<&Code<'a>> {
         match self {
             Self::Untagged(StatusBody { code, .. })
             | Self::Tagged(Tagged {
@@ -312,6 +403,149 @@ impl<'a> Status<'a> {
             | Self::Bye(Bye { text, .. }) => text,
         }
     }
+
+    /// Returns the capabilities of a `[CAPABILITY ...]` code, if any.
+    ///
+    /// Servers may include capabilities in a status, e.g., in the tagged completion of `LOGIN`
+    /// (`A1 OK [CAPABILITY IMAP4rev1 ...] Logged in`). This saves a separate `CAPABILITY` command.
+    pub fn capabilities(&self) -> Option<&[Capability<'a>]> {
+        match self.code() {
+            Some(Code::Capability(capabilities)) => Some(capabilities.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// Returns the text of an `[ALERT]`, if any.
+    ///
+    /// The text of an alert must be presented to the user in a way that calls their attention to
+    /// the message.
+    pub fn alert_text(&self) -> Option<&str> {
+        match self.code() {
+            Some(Code::Alert) => Some(self.text().as_ref()),
+            _ => None,
+        }
+    }
+
+    // ---------------------------------------------------------------------------------------------
+
+    /// Returns `true` for a tagged status, i.e., the completion result of a command.
+    pub fn is_tagged(&self) -> bool {
+        matches!(self, Self::Tagged(_))
+    }
+
+    /// Returns `true` for an untagged (informational) status, including BYE.
+    pub fn is_untagged(&self) -> bool {
+        !self.is_tagged()
+    }
+
+    /// Returns `true` for a (tagged or untagged) OK.
+    pub fn is_ok(&self) -> bool {
+        self.kind() == Some(StatusKind::Ok)
+    }
+
+    /// Returns `true` for a (tagged or untagged) NO.
+    pub fn is_no(&self) -> bool {
+        self.kind() == Some(StatusKind::No)
+    }
+
+    /// Returns `true` for a (tagged or untagged) BAD.
+    pub fn is_bad(&self) -> bool {
+        self.kind() == Some(StatusKind::Bad)
+    }
+
+    /// Returns `true` for a BYE.
+    pub fn is_bye(&self) -> bool {
+        matches!(self, Self::Bye(_))
+    }
+
+    fn kind(&self) -> Option<StatusKind> {
+        match self {
+            Self::Untagged(StatusBody { kind, .. })
+            | Self::Tagged(Tagged {
+                body: StatusBody { kind, .. },
+                ..
+            }) => Some(*kind),
+            Self::Bye(_) => None,
+        }
+    }
+}
+
+/// Builder for [`Status`] responses.
+///
+/// ```rust
+/// use imap_types::{
+///     core::Tag,
+///     response::{Code, Status, StatusBuilder},
+/// };
+///
+/// let status = StatusBuilder::ok(Some(Tag::try_from("A1").unwrap()))
+///     .code(Code::ReadWrite)
+///     .text("Select completed")
+///     .unwrap();
+///
+/// assert_eq!(
+///     status,
+///     Status::ok(
+///         Some(Tag::try_from("A1").unwrap()),
+///         Some(Code::ReadWrite),
+///         "Select completed"
+///     )
+///     .unwrap()
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StatusBuilder<'a> {
+    tag: Option<Tag<'a>>,
+    /// `None` means BYE.
+    kind: Option<StatusKind>,
+    code: Option<Code<'a>>,
+}
+
+impl<'a> StatusBuilder<'a> {
+    /// Start an OK status (tagged when `tag` is `Some`).
+    pub fn ok(tag: Option<Tag<'a>>) -> Self {
+        Self::new(tag, Some(StatusKind::Ok))
+    }
+
+    /// Start a NO status (tagged when `tag` is `Some`).
+    pub fn no(tag: Option<Tag<'a>>) -> Self {
+        Self::new(tag, Some(StatusKind::No))
+    }
+
+    /// Start a BAD status (tagged when `tag` is `Some`).
+    pub fn bad(tag: Option<Tag<'a>>) -> Self {
+        Self::new(tag, Some(StatusKind::Bad))
+    }
+
+    /// Start a BYE status.
+    pub fn bye() -> Self {
+        Self::new(None, None)
+    }
+
+    fn new(tag: Option<Tag<'a>>, kind: Option<StatusKind>) -> Self {
+        Self {
+            tag,
+            kind,
+            code: None,
+        }
+    }
+
+    /// Set the response code.
+    pub fn code(mut self, code: Code<'a>) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Finish the status with a human-readable `text`.
+    pub fn text<T>(self, text: T) -> Result<Status<'a>, T::Error>
+    where
+        T: TryInto<Text<'a>>,
+    {
+        match self.kind {
+            Some(kind) => Status::new(self.tag, kind, self.code, text),
+            None => Status::bye(self.code, text),
+        }
+    }
 }
 
 /// ## 7.2 - 7.4 Server and Mailbox Status; Mailbox Size; Message Status
@@ -404,6 +638,8 @@ pub enum Data<'a> {
     /// returns a single name that matches the LSUB specification.  There
     /// can be multiple LSUB responses for a single LSUB command.  The
     /// data is identical in format to the LIST response.
+    ///
+    /// Note: LIST-EXTENDED (RFC 5258) supersedes LSUB, but many servers still use it.
     Lsub {
         /// Name attributes
         items: Vec<FlagNameAttribute<'a>>,
@@ -434,7 +670,16 @@ pub enum Data<'a> {
     /// search criteria.  For SEARCH, these are message sequence numbers;
     /// for UID SEARCH, these are unique identifiers.  Each number is
     /// delimited by a space.
-    Search(Vec<NonZeroU32>),
+    Search {
+        /// Message sequence numbers or UIDs.
+        numbers: Vec<NonZeroU32>,
+        /// Highest mod-sequence of all messages in the result (RFC 7162).
+        ///
+        /// Only sent when the `SEARCH` command used the `MODSEQ` criterion.
+        ///
+        /// Note: The grammar requires at least one number, i.e., `modseq` is not encoded when `numbers` is empty.
+        modseq: Option<NonZeroU64>,
+    },
 
     #[cfg(feature = "ext_sort_thread")]
     Sort(Vec<NonZeroU32>),
@@ -545,8 +790,12 @@ pub enum Data<'a> {
     /// parentheses.  This response occurs as the result of a FETCH or
     /// STORE command, as well as by unilateral server decision (e.g.,
     /// flag updates).
+    ///
+    /// Note: The number preceding `FETCH` is always a message sequence number, even in response to
+    /// `UID FETCH`. The UID, if any, is sent as `UID` data item. See [`Data::seq_number`] and
+    /// [`Data::uid`].
     Fetch {
-        /// Sequence number.
+        /// Message sequence number (never a UID).
         seq: NonZeroU32,
         /// Message data items.
         items: Vec1<MessageDataItem<'a>>,
@@ -574,6 +823,9 @@ pub enum Data<'a> {
     /// ID Response
     Id {
         /// Parameters
+        ///
+        /// `None` is encoded as `NIL` ("I decline to identify"), whereas `Some(vec![])` is
+        /// encoded as `()`.
         parameters: Option<Vec<(IString<'a>, NString<'a>)>>,
     },
 
@@ -593,30 +845,65 @@ impl<'a> Data<'a> {
         Ok(Self::Capability(caps.try_into()?))
     }
 
-    // TODO
-    // pub fn list() -> Self {
-    //     unimplemented!()
-    // }
+    pub fn list<M>(
+        items: Vec<FlagNameAttribute<'a>>,
+        delimiter: Option<QuotedChar>,
+        mailbox: M,
+    ) -> Result<Self, M::Error>
+    where
+        M: TryInto<Mailbox<'a>>,
+    {
+        Ok(Self::List {
+            items,
+            delimiter,
+            mailbox: mailbox.try_into()?,
+        })
+    }
 
-    // TODO
-    // pub fn lsub() -> Self {
-    //     unimplemented!()
-    // }
+    pub fn lsub<M>(
+        items: Vec<FlagNameAttribute<'a>>,
+        delimiter: Option<QuotedChar>,
+        mailbox: M,
+    ) -> Result<Self, M::Error>
+    where
+        M: TryInto<Mailbox<'a>>,
+    {
+        Ok(Self::Lsub {
+            items,
+            delimiter,
+            mailbox: mailbox.try_into()?,
+        })
+    }
 
-    // TODO
-    // pub fn status() -> Self {
-    //     unimplemented!()
-    // }
+    pub fn status<M, I>(mailbox: M, items: I) -> Result<Self, M::Error>
+    where
+        M: TryInto<Mailbox<'a>>,
+        I: Into<Cow<'a, [StatusDataItem]>>,
+    {
+        Ok(Self::Status {
+            mailbox: mailbox.try_into()?,
+            items: items.into(),
+        })
+    }
 
-    // TODO
-    // pub fn search() -> Self {
-    //     unimplemented!()
-    // }
+    pub fn search(numbers: Vec<NonZeroU32>) -> Self {
+        Self::Search {
+            numbers,
+            modseq: None,
+        }
+    }
 
-    // TODO
-    // pub fn flags() -> Self {
-    //     unimplemented!()
-    // }
+    pub fn flags(flags: Vec<Flag<'a>>) -> Self {
+        Self::Flags(flags)
+    }
+
+    pub fn exists(count: u32) -> Self {
+        Self::Exists(count)
+    }
+
+    pub fn recent(count: u32) -> Self {
+        Self::Recent(count)
+    }
 
     pub fn expunge(seq: u32) -> Result<Self, TryFromIntError> {
         Ok(Self::Expunge(NonZeroU32::try_from(seq)?))
@@ -632,6 +919,81 @@ impl<'a> Data<'a> {
 
         Ok(Self::Fetch { seq, items })
     }
+
+    /// Get the message sequence number of a FETCH response.
+    ///
+    /// This is never a UID, not even in response to `UID FETCH`. Use [`Data::uid`] to get the UID.
+    pub fn seq_number(&self) -> Option<NonZeroU32> {
+        match self {
+            Self::Fetch { seq, .. } => Some(*seq),
+            _ => None,
+        }
+    }
+
+    /// Get the UID of a FETCH response, i.e., the value of its `UID` data item.
+    ///
+    /// Servers include the UID in every response to `UID FETCH` (and `UID STORE`), but may omit it
+    /// otherwise.
+    pub fn uid(&self) -> Option<NonZeroU32> {
+        match self {
+            Self::Fetch { items, .. } => items.uid(),
+            _ => None,
+        }
+    }
+
+    /// Get the sequence number and flags of a flag update.
+    ///
+    /// A flag update is a FETCH response containing only FLAGS (and optionally UID), e.g.,
+    /// `* 12 FETCH (FLAGS (\\Seen \\Deleted))`. Servers send these after a STORE or (unsolicited)
+    /// when another session changed the flags of a message.
+    ///
+    /// When FLAGS occurs more than once, the last occurrence is returned, as with all accessors of
+    /// `Vec1<MessageDataItem>`.
+    pub fn flag_update(&self) -> Option<(NonZeroU32, &[FlagFetch<'a>])> {
+        let (seq, items) = match self {
+            Self::Fetch { seq, items } => (seq, items),
+            _ => return None,
+        };
+
+        let only_flags = items
+            .as_ref()
+            .iter()
+            .all(|item| matches!(item, MessageDataItem::Flags(_) | MessageDataItem::Uid(_)));
+
+        if !only_flags {
+            return None;
+        }
+
+        items.flags().map(|flags| (*seq, flags))
+    }
+
+    /// Get all vendor-specific capabilities of a CAPABILITY response.
+    ///
+    /// See [`Capability::is_vendor_extension`].
+    pub fn vendor_extensions(&self) -> impl Iterator<Item = &CapabilityOther<'a>> {
+        let capabilities = match self {
+            Self::Capability(capabilities) => capabilities.as_ref(),
+            _ => &[],
+        };
+
+        capabilities
+            .iter()
+            .filter_map(|capability| match capability {
+                Capability::Other(other) if capability.is_vendor_extension() => Some(other),
+                _ => None,
+            })
+    }
+
+    /// Get the hierarchy delimiter of a LIST or LSUB response.
+    ///
+    /// Returns `None` for other responses or when the server sent `NIL`, i.e., there is no
+    /// hierarchy. Clients commonly discover the delimiter via `LIST "" ""`.
+    pub fn hierarchy_delimiter(&self) -> Option<QuotedChar> {
+        match self {
+            Self::List { delimiter, .. } | Self::Lsub { delimiter, .. } => *delimiter,
+            _ => None,
+        }
+    }
 }
 
 /// ## 7.5. Server Responses - Command Continuation Request
@@ -874,6 +1236,10 @@ pub enum Code<'a> {
     #[cfg(feature = "ext_uidplus")]
     UidNotSticky,
 
+    #[cfg(feature = "ext_special_use")]
+    /// Server can't assign the requested special-use attribute(s) to the mailbox.
+    UseAttr,
+
     /// Additional response codes defined by particular client or server
     /// implementations SHOULD be prefixed with an "X" until they are
     /// added to a revision of this protocol.  Client implementations
@@ -966,6 +1332,108 @@ impl<'a> CodeOther<'a> {
     pub fn inner(&self) -> &[u8] {
         self.0.as_ref()
     }
+
+    /// Name of the code, i.e., everything before the first space.
+    pub fn name(&self) -> &[u8] {
+        self.split().0
+    }
+
+    /// Parameters of the code, i.e., everything after the first space (if any).
+    pub fn params(&self) -> Option<&[u8]> {
+        self.split().1
+    }
+
+    fn split(&self) -> (&[u8], Option<&[u8]>) {
+        let inner = self.inner();
+
+        match inner.iter().position(|byte| *byte == b' ') {
+            Some(position) => (&inner[..position], Some(&inner[position + 1..])),
+            None => (inner, None),
+        }
+    }
+}
+
+/// Parser for an unknown code registered in a [`CodeRegistry`].
+///
+/// The parser receives the parameters of the code (see [`CodeOther::params`]).
+pub type CodeParser<T> = fn(Option<&[u8]>) -> Option<T>;
+
+/// Registry of parsers for codes not supported by this crate, e.g., vendor-specific codes.
+///
+/// Unknown codes are represented as [`Code::Other`]. A registry recognizes them by name and turns
+/// them into a user-defined type `T`.
+///
+/// # Example
+///
+/// ```
+/// use imap_types::response::{Code, CodeRegistry};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum VendorCode {
+///     Throttled { seconds: u32 },
+///     Maintenance,
+/// }
+///
+/// let registry = CodeRegistry::new()
+///     .register("X-THROTTLED", |params| {
+///         let seconds = std::str::from_utf8(params?).ok()?.parse().ok()?;
+///         Some(VendorCode::Throttled { seconds })
+///     })
+///     .register("X-MAINTENANCE", |_| Some(VendorCode::Maintenance));
+///
+/// // `Code::Other` is usually obtained by parsing a response.
+/// # fn code() -> Code<'static> { Code::Alert }
+/// let code = code();
+///
+/// match registry.parse(&code) {
+///     Some(VendorCode::Throttled { seconds }) => { /* ... */ }
+///     Some(VendorCode::Maintenance) => { /* ... */ }
+///     None => { /* Not a registered code. */ }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CodeRegistry<T> {
+    parsers: Vec<(&'static str, CodeParser<T>)>,
+}
+
+impl<T> Default for CodeRegistry<T> {
+    fn default() -> Self {
+        Self {
+            parsers: Vec::new(),
+        }
+    }
+}
+
+impl<T> CodeRegistry<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a parser for the code `name` (case-insensitive).
+    ///
+    /// A later registration of the same name takes precedence.
+    pub fn register(mut self, name: &'static str, parser: CodeParser<T>) -> Self {
+        self.parsers.insert(0, (name, parser));
+        self
+    }
+
+    /// Parse a code using the registered parsers.
+    ///
+    /// Returns `None` when `code` is not a [`Code::Other`], no parser is registered for its name,
+    /// or the registered parser rejected the parameters.
+    pub fn parse(&self, code: &Code) -> Option<T> {
+        let other = match code {
+            Code::Other(other) => other,
+            _ => return None,
+        };
+
+        let (_, parser) = self
+            .parsers
+            .iter()
+            .find(|(name, _)| name.as_bytes().eq_ignore_ascii_case(other.name()))?;
+
+        parser(other.params())
+    }
 }
 
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
@@ -1029,10 +1497,29 @@ pub enum Capability<'a> {
     #[cfg(feature = "ext_uidplus")]
     /// UIDPLUS extension (RFC 4351)
     UidPlus,
+    #[cfg(feature = "ext_preview")]
+    /// See RFC 8970.
+    Preview,
+    #[cfg(feature = "ext_replace")]
+    /// See RFC 8508.
+    Replace,
     /// Other/Unknown
     Other(CapabilityOther<'a>),
 }
 
+impl<'a> Capability<'a> {
+    /// Check if this is an experimental or vendor-specific capability, e.g., `X-GM-EXT-1`.
+    ///
+    /// RFC 3501 requires these capabilities to start with "X". They are always represented as
+    /// [`Capability::Other`] with their original casing.
+    pub fn is_vendor_extension(&self) -> bool {
+        match self {
+            Self::Other(other) => other.as_ref().starts_with(['X', 'x']),
+            _ => false,
+        }
+    }
+}
+
 impl<'a> Display for Capability<'a> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         match self {
@@ -1073,6 +1560,10 @@ impl<'a> Display for Capability<'a> {
             Self::Binary => write!(f, "BINARY"),
             #[cfg(feature = "ext_uidplus")]
             Self::UidPlus => write!(f, "UIDPLUS"),
+            #[cfg(feature = "ext_preview")]
+            Self::Preview => write!(f, "PREVIEW"),
+            #[cfg(feature = "ext_replace")]
+            Self::Replace => write!(f, "REPLACE"),
             Self::Other(other) => write!(f, "{}", other.0),
         }
     }
@@ -1110,7 +1601,10 @@ impl<'a> From<Atom<'a>> for Capability<'a> {
 
         let cow = atom.into_inner();
 
-        match cow.to_ascii_lowercase().as_ref() {
+        // All known capabilities are shorter than this.
+        let mut buffer = [0u8; 32];
+
+        match to_ascii_lowercase_in(&cow, &mut buffer).unwrap_or_default() {
             "imap4rev1" => Self::Imap4Rev1,
             #[cfg(feature = "starttls")]
             "logindisabled" => Self::LoginDisabled,
@@ -1141,10 +1635,14 @@ impl<'a> From<Atom<'a>> for Capability<'a> {
             "unselect" => Self::Unselect,
             #[cfg(feature = "ext_uidplus")]
             "uidplus" => Self::UidPlus,
+            #[cfg(feature = "ext_preview")]
+            "preview" => Self::Preview,
+            #[cfg(feature = "ext_replace")]
+            "replace" => Self::Replace,
             _ => {
                 // TODO(efficiency)
                 if let Some((left, right)) = split_once_cow(cow.clone(), "=") {
-                    match left.as_ref().to_ascii_lowercase().as_ref() {
+                    match to_ascii_lowercase_in(&left, &mut buffer).unwrap_or_default() {
                         "auth" => {
                             if let Ok(mechanism) = AuthMechanism::try_from(right) {
                                 return Self::Auth(mechanism);
@@ -1158,11 +1656,22 @@ impl<'a> From<Atom<'a>> for Capability<'a> {
                             }
                         }
                         "quota" => {
-                            if let Some((_, right)) =
-                                right.as_ref().to_ascii_lowercase().split_once("res-")
-                            {
-                                // TODO(efficiency)
-                                if let Ok(resource) = Resource::try_from(right.to_owned()) {
+                            // Note: `get` returns `None` if there is no char boundary after "res-".
+                            let is_res = matches!(
+                                right.get(..4),
+                                Some(prefix) if prefix.eq_ignore_ascii_case("res-")
+                            );
+
+                            if is_res {
+                                let resource = match right {
+                                    Cow::Borrowed(str) => Cow::Borrowed(&str[4..]),
+                                    Cow::Owned(mut string) => {
+                                        string.drain(..4);
+                                        Cow::Owned(string)
+                                    }
+                                };
+
+                                if let Ok(resource) = Resource::try_from(resource) {
                                     return Self::QuotaRes(resource);
                                 }
                             }
@@ -1197,6 +1706,134 @@ impl<'a> From<Atom<'a>> for Capability<'a> {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CapabilityOther<'a>(Atom<'a>);
 
+impl<'a> AsRef<str> for CapabilityOther<'a> {
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+/// A deduplicated set of capabilities that preserves the order in which they were first seen.
+///
+/// Useful to combine the capabilities announced in the greeting with those from a later
+/// `CAPABILITY` response. Capabilities are deduplicated case-insensitively (see
+/// [`eq_capability`](crate::casing::eq_capability)), e.g., `IMAP4rev1` and `imap4REV1` as well as
+/// `X-FOO` and `x-foo` are the same. The first spelling is kept.
+///
+/// Note: The derived `PartialEq` and `Hash` compare the capabilities as-is, i.e., they depend on
+/// the order and spelling of the capabilities. Use [`Capabilities::contains`] to check whether a
+/// capability was advertised.
+///
+/// # Example
+///
+/// ```
+/// use imap_types::response::{Capabilities, Capability};
+///
+/// let mut capabilities = Capabilities::new([Capability::Imap4Rev1, Capability::Idle]);
+/// capabilities.merge([Capability::Move, Capability::Imap4Rev1]);
+///
+/// assert_eq!(
+///     capabilities.as_ref(),
+///     [Capability::Imap4Rev1, Capability::Idle, Capability::Move]
+/// );
+/// ```
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Capabilities<'a>(Vec<Capability<'a>>);
+
+impl<'a> Capabilities<'a> {
+    /// Create a set of capabilities, dropping duplicates.
+    pub fn new<I>(capabilities: I) -> Self
+    where
+        I: IntoIterator<Item = Capability<'a>>,
+    {
+        let mut this = Self::default();
+        this.merge(capabilities);
+        this
+    }
+
+    /// Add all capabilities not already contained (keeping the order).
+    pub fn merge<I>(&mut self, capabilities: I)
+    where
+        I: IntoIterator<Item = Capability<'a>>,
+    {
+        for capability in capabilities {
+            if !self.contains(&capability) {
+                self.0.push(capability);
+            }
+        }
+    }
+
+    /// Returns `true` when `capability` was advertised (compared case-insensitively).
+    pub fn contains(&self, capability: &Capability) -> bool {
+        self.0.iter().any(|known| eq_capability(known, capability))
+    }
+
+    /// Merge the capabilities advertised in `response`, if any.
+    ///
+    /// Servers may send capabilities at any time, e.g., as unsolicited `* CAPABILITY ...` data or as
+    /// `[CAPABILITY ...]` code in a status response after authentication.
+    /// Returns `true` when `response` advertised capabilities.
+    ///
+    /// Note: After `STARTTLS`, previously cached capabilities must be discarded. Start with a fresh
+    /// set in this case.
+    pub fn update(&mut self, response: &Response<'a>) -> bool {
+        let capabilities = match response {
+            Response::Data(Data::Capability(capabilities)) => capabilities.as_ref(),
+            Response::Status(status) => match status.capabilities() {
+                Some(capabilities) => capabilities,
+                None => return false,
+            },
+            _ => return false,
+        };
+
+        self.merge(capabilities.iter().cloned());
+
+        true
+    }
+
+    /// Returns `false` when the server advertised `LOGINDISABLED`.
+    ///
+    /// A client must not send `LOGIN` in this case, e.g., before `STARTTLS` was issued.
+    /// Without the `starttls` feature, `LOGINDISABLED` is a [`Capability::Other`] and still honored.
+    pub fn login_allowed(&self) -> bool {
+        !self.0.iter().any(|capability| match capability {
+            #[cfg(feature = "starttls")]
+            Capability::LoginDisabled => true,
+            Capability::Other(other) => other.as_ref().eq_ignore_ascii_case("LOGINDISABLED"),
+            _ => false,
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Capability<'a>> {
+        self.0.iter()
+    }
+
+    pub fn into_inner(self) -> Vec<Capability<'a>> {
+        self.0
+    }
+}
+
+impl<'a> AsRef<[Capability<'a>]> for Capabilities<'a> {
+    fn as_ref(&self) -> &[Capability<'a>] {
+        &self.0
+    }
+}
+
+impl<'a> FromIterator<Capability<'a>> for Capabilities<'a> {
+    fn from_iter<I: IntoIterator<Item = Capability<'a>>>(iter: I) -> Self {
+        Self::new(iter)
+    }
+}
+
+impl<'a> IntoIterator for Capabilities<'a> {
+    type Item = Capability<'a>;
+    type IntoIter = std::vec::IntoIter<Capability<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
 /// Error-related types.
 pub mod error {
     use thiserror::Error;
@@ -1228,6 +1865,339 @@ mod tests {
         let _ = Data::fetch(1, vec![MessageDataItem::Rfc822Size(123)]).unwrap();
     }
 
+    #[test]
+    fn test_response_tag() {
+        let tag = Tag::try_from("A1").unwrap();
+
+        let tests = [
+            (
+                Response::Status(Status::ok(Some(tag.clone()), None, "done").unwrap()),
+                Some(&tag),
+            ),
+            (
+                Response::Status(Status::ok(None, None, "hello").unwrap()),
+                None,
+            ),
+            (Response::Status(Status::bye(None, "bye").unwrap()), None),
+            (Response::Data(Data::Exists(42)), None),
+            (
+                Response::CommandContinuationRequest(
+                    CommandContinuationRequest::basic(None, "Ready for literal").unwrap(),
+                ),
+                None,
+            ),
+        ];
+
+        for (test, expected) in tests.iter() {
+            assert_eq!(test.tag(), *expected);
+        }
+    }
+
+    #[test]
+    fn test_status_classifiers() {
+        let tag = || Some(Tag::try_from("A").unwrap());
+
+        // (status, tagged, ok, no, bad, bye)
+        let tests = [
+            (
+                Status::ok(tag(), None, "x").unwrap(),
+                true,
+                true,
+                false,
+                false,
+                false,
+            ),
+            (
+                Status::no(tag(), None, "x").unwrap(),
+                true,
+                false,
+                true,
+                false,
+                false,
+            ),
+            (
+                Status::bad(tag(), None, "x").unwrap(),
+                true,
+                false,
+                false,
+                true,
+                false,
+            ),
+            (
+                Status::ok(None, None, "x").unwrap(),
+                false,
+                true,
+                false,
+                false,
+                false,
+            ),
+            (
+                Status::no(None, None, "x").unwrap(),
+                false,
+                false,
+                true,
+                false,
+                false,
+            ),
+            (
+                Status::bad(None, None, "x").unwrap(),
+                false,
+                false,
+                false,
+                true,
+                false,
+            ),
+            (
+                Status::bye(None, "x").unwrap(),
+                false,
+                false,
+                false,
+                false,
+                true,
+            ),
+        ];
+
+        for (status, tagged, ok, no, bad, bye) in tests {
+            assert_eq!(status.is_tagged(), tagged);
+            assert_eq!(status.is_untagged(), !tagged);
+            assert_eq!(status.is_ok(), ok);
+            assert_eq!(status.is_no(), no);
+            assert_eq!(status.is_bad(), bad);
+            assert_eq!(status.is_bye(), bye);
+        }
+    }
+
+    #[test]
+    fn test_capability_from_atom() {
+        let long = "X".repeat(64);
+
+        let tests = [
+            ("IMAP4rev1", Capability::Imap4Rev1),
+            ("imap4REV1", Capability::Imap4Rev1),
+            ("Literal+", Capability::LiteralPlus),
+            ("auth=plain", Capability::Auth(AuthMechanism::Plain)),
+            ("AUTH=PLAIN", Capability::Auth(AuthMechanism::Plain)),
+            ("QUOTA=RES-storage", Capability::QuotaRes(Resource::Storage)),
+            (
+                "quota=res-X-Foo",
+                Capability::QuotaRes(Resource::try_from("X-Foo").unwrap()),
+            ),
+            (
+                "QUOTA=XRES-STORAGE",
+                Capability::Other(CapabilityOther(
+                    Atom::try_from("QUOTA=XRES-STORAGE").unwrap(),
+                )),
+            ),
+            (
+                "IMAP4rev1X",
+                Capability::Other(CapabilityOther(Atom::try_from("IMAP4rev1X").unwrap())),
+            ),
+            (
+                long.as_str(),
+                Capability::Other(CapabilityOther(Atom::try_from(long.as_str()).unwrap())),
+            ),
+        ];
+
+        for (test, expected) in tests {
+            assert_eq!(Capability::try_from(test).unwrap(), expected, "{test}");
+        }
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let capability = |value: &'static str| Capability::try_from(value).unwrap();
+
+        let greeting = Vec1::try_from(vec![
+            capability("IMAP4rev1"),
+            capability("AUTH=PLAIN"),
+            capability("imap4REV1"),
+            capability("X-FOO"),
+            capability("X-FOO"),
+        ])
+        .unwrap();
+
+        let mut capabilities = Capabilities::new(greeting);
+        assert_eq!(
+            capabilities.as_ref(),
+            [
+                Capability::Imap4Rev1,
+                Capability::Auth(AuthMechanism::Plain),
+                capability("X-FOO"),
+            ]
+        );
+
+        assert!(capabilities.contains(&capability("x-FOO")));
+        // `==` depends on spelling and order.
+        assert_ne!(
+            capabilities,
+            Capabilities::new([
+                Capability::Auth(AuthMechanism::Plain),
+                Capability::Imap4Rev1,
+                capability("x-foo"),
+            ])
+        );
+
+        capabilities.merge([
+            capability("IDLE"),
+            capability("auth=plain"),
+            capability("x-foo"),
+            capability("Idle"),
+        ]);
+        assert_eq!(
+            capabilities.into_inner(),
+            [
+                Capability::Imap4Rev1,
+                Capability::Auth(AuthMechanism::Plain),
+                capability("X-FOO"),
+                Capability::Idle,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_capabilities_update() {
+        let mut capabilities = Capabilities::new([Capability::Imap4Rev1]);
+
+        assert!(
+            capabilities.update(&Response::Data(Data::Capability(Vec1::from(
+                Capability::Idle
+            ))))
+        );
+        assert!(capabilities.update(&Response::Status(
+            Status::ok(
+                None,
+                Some(Code::Capability(Vec1::from(Capability::Move))),
+                "..."
+            )
+            .unwrap()
+        )));
+        assert!(!capabilities.update(&Response::Status(
+            Status::ok(None, Some(Code::Alert), "...").unwrap()
+        )));
+        assert!(!capabilities.update(&Response::Data(Data::Exists(1))));
+
+        assert_eq!(
+            capabilities.as_ref(),
+            [Capability::Imap4Rev1, Capability::Idle, Capability::Move]
+        );
+    }
+
+    #[test]
+    fn test_capabilities_login_allowed() {
+        #[cfg(feature = "starttls")]
+        {
+            let capabilities: Capabilities = [Capability::Imap4Rev1, Capability::LoginDisabled]
+                .into_iter()
+                .collect();
+            assert!(!capabilities.login_allowed());
+        }
+
+        let capabilities = Capabilities::new([
+            Capability::Imap4Rev1,
+            Capability::try_from("logindisabled").unwrap(),
+        ]);
+        assert!(!capabilities.login_allowed());
+
+        let capabilities = Capabilities::new([Capability::Imap4Rev1, Capability::Idle]);
+        assert!(capabilities.login_allowed());
+    }
+
+    #[test]
+    fn test_capability_is_vendor_extension() {
+        let tests = [
+            ("X-GM-EXT-1", true),
+            ("xlist", true),
+            ("IMAP4rev1", false),
+            ("AUTH=XOAUTH2", false),
+            ("UNKNOWN", false),
+        ];
+
+        for (test, expected) in tests {
+            let capability = Capability::try_from(test).unwrap();
+            assert_eq!(capability.is_vendor_extension(), expected, "{test}");
+
+            if expected {
+                // Casing is preserved.
+                assert_eq!(capability.to_string(), test);
+            }
+        }
+    }
+
+    #[test]
+    fn test_flag_update() {
+        let flags = vec![FlagFetch::Flag(Flag::Seen), FlagFetch::Flag(Flag::Deleted)];
+
+        let data = Data::fetch(12, vec![MessageDataItem::Flags(flags.clone())]).unwrap();
+        assert_eq!(
+            data.flag_update(),
+            Some((NonZeroU32::new(12).unwrap(), flags.as_slice()))
+        );
+
+        let data = Data::fetch(
+            12,
+            vec![
+                MessageDataItem::Uid(NonZeroU32::new(42).unwrap()),
+                MessageDataItem::Flags(vec![]),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            data.flag_update(),
+            Some((NonZeroU32::new(12).unwrap(), [].as_slice()))
+        );
+
+        // The last FLAGS item wins.
+        let data = Data::fetch(
+            12,
+            vec![
+                MessageDataItem::Flags(vec![]),
+                MessageDataItem::Uid(NonZeroU32::new(42).unwrap()),
+                MessageDataItem::Flags(flags.clone()),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            data.flag_update(),
+            Some((NonZeroU32::new(12).unwrap(), flags.as_slice()))
+        );
+
+        let tests = [
+            Data::fetch(12, vec![MessageDataItem::Uid(NonZeroU32::new(42).unwrap())]).unwrap(),
+            Data::fetch(
+                12,
+                vec![
+                    MessageDataItem::Flags(flags),
+                    MessageDataItem::Rfc822Size(123),
+                ],
+            )
+            .unwrap(),
+            Data::Exists(12),
+        ];
+
+        for test in tests {
+            assert_eq!(test.flag_update(), None);
+        }
+    }
+
+    #[test]
+    fn test_hierarchy_delimiter() {
+        let data = Data::List {
+            items: vec![],
+            delimiter: Some(QuotedChar::try_from('/').unwrap()),
+            mailbox: "".try_into().unwrap(),
+        };
+        assert_eq!(data.hierarchy_delimiter().map(|c| c.inner()), Some('/'));
+
+        let data = Data::Lsub {
+            items: vec![],
+            delimiter: None,
+            mailbox: "".try_into().unwrap(),
+        };
+        assert_eq!(data.hierarchy_delimiter(), None);
+
+        assert_eq!(Data::Exists(1).hierarchy_delimiter(), None);
+    }
+
     #[test]
     fn test_conversion_continue_failing() {
         let tests = [
@@ -13,6 +13,9 @@ use bounded_static::ToStatic;
 use serde::{Deserialize, Serialize};
 
 /// A wrapper to ensure that secrets are redacted during `Debug`-printing.
+///
+/// Secrets are redacted regardless of the build profile, so that logging a debug build doesn't leak
+/// credentials either. Use [`Secret::declassify`] to access the inner value.
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
@@ -37,15 +40,9 @@ impl<T> From<T> for Secret<T> {
     }
 }
 
-impl<T> Debug for Secret<T>
-where
-    T: Debug,
-{
+impl<T> Debug for Secret<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        #[cfg(not(debug_assertions))]
-        return write!(f, "/* REDACTED */");
-        #[cfg(debug_assertions)]
-        return self.0.fmt(f);
+        write!(f, "/* REDACTED */")
     }
 }
 
@@ -57,8 +54,6 @@ mod tests {
     };
 
     #[test]
-    #[cfg(not(debug_assertions))]
-    #[allow(clippy::redundant_clone)]
     fn test_that_secret_is_redacted() {
         use super::Secret;
         use crate::auth::{AuthMechanism, AuthenticateData};
@@ -106,6 +101,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_that_login_password_is_redacted() {
+        let command = CommandBody::login("alice", "hunter2")
+            .unwrap()
+            .tag("A")
+            .unwrap();
+
+        let got = format!("{:?}", command);
+        assert!(got.contains("alice"));
+        assert!(!got.contains("hunter2"));
+
+        let got = format!("{:#?}", command);
+        assert!(got.contains("alice"));
+        assert!(!got.contains("hunter2"));
+
+        match command.body {
+            CommandBody::Login { password, .. } => assert_eq!(
+                password.declassify(),
+                &AString::from(Atom::try_from("hunter2").unwrap())
+            ),
+            _ => unreachable!(),
+        }
+    }
+
     #[test]
     fn test_that_secret_has_no_side_effects_on_eq() {
         assert_ne!(
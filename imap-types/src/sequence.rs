@@ -15,8 +15,9 @@ use bounded_static::ToStatic;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use self::error::StrictSequenceSetError;
 use crate::{
-    core::Vec1,
+    core::{Vec1, VecN},
     error::{ValidationError, ValidationErrorKind},
 };
 
@@ -125,7 +126,6 @@ impl FromStr for SequenceSet {
     }
 }
 
-#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -500,15 +500,199 @@ impl<'a> SequenceSet {
             active_range: None,
         }
     }
+
+    /// Split the sequence set into chunks whose encoded form is at most `max_encoded_len` bytes.
+    ///
+    /// Useful to split, e.g., a `FETCH` of many UIDs into multiple commands when servers limit the
+    /// command line length. Sequences are neither reordered nor split, i.e., the chunks joined by
+    /// `,` equal the original sequence set. Thus, a chunk only exceeds `max_encoded_len` when it
+    /// consists of a single sequence that is already too long.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use imap_types::sequence::SequenceSet;
+    ///
+    /// let seq = SequenceSet::try_from("1:10,20,30:40,50").unwrap();
+    ///
+    /// assert_eq!(
+    ///     seq.chunks(8).collect::<Vec<_>>(),
+    ///     [
+    ///         SequenceSet::try_from("1:10,20").unwrap(),
+    ///         SequenceSet::try_from("30:40,50").unwrap(),
+    ///     ]
+    /// );
+    /// ```
+    pub fn chunks(&self, max_encoded_len: usize) -> impl Iterator<Item = SequenceSet> {
+        let mut chunks = Vec::new();
+        let mut chunk: Vec<Sequence> = Vec::new();
+        let mut chunk_len = 0;
+
+        for sequence in self.0.as_ref() {
+            let len = sequence.encoded_len();
+
+            // Account for the `,` separator.
+            if !chunk.is_empty() && chunk_len + 1 + len > max_encoded_len {
+                chunks.push(std::mem::take(&mut chunk));
+                chunk_len = 0;
+            }
+
+            chunk_len += if chunk.is_empty() { len } else { 1 + len };
+            chunk.push(sequence.clone());
+        }
+
+        chunks.push(chunk);
+
+        // Every chunk contains at least one sequence.
+        chunks.into_iter().map(|chunk| SequenceSet(VecN(chunk)))
+    }
+
+    /// Validate that the sequence set is sorted in ascending order and free of overlaps.
+    ///
+    /// IMAP allows arbitrary sequence sets, e.g., `1:1000000,1:1000000,...`. A server may use this
+    /// method to reject such sets, e.g., to not let clients amplify work. `*` is treated as the
+    /// largest number. Adjacent sequences, e.g., `1,2,3`, are allowed. A reversed range, e.g.,
+    /// `5:1`, is equivalent to `1:5`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use imap_types::sequence::{error::StrictSequenceSetError, SequenceSet};
+    ///
+    /// assert!(SequenceSet::try_from("1,3:5,7:*")
+    ///     .unwrap()
+    ///     .validate_strict()
+    ///     .is_ok());
+    /// assert_eq!(
+    ///     SequenceSet::try_from("1:5,3:7").unwrap().validate_strict(),
+    ///     Err(StrictSequenceSetError::Unordered { index: 1 })
+    /// );
+    /// ```
+    pub fn validate_strict(&self) -> Result<(), StrictSequenceSetError> {
+        // Sort `*` after every number.
+        fn key(seq_or_uid: &SeqOrUid) -> u64 {
+            match seq_or_uid {
+                SeqOrUid::Value(value) => u64::from(value.get()),
+                SeqOrUid::Asterisk => u64::MAX,
+            }
+        }
+
+        let mut previous_end = None;
+
+        for (index, sequence) in self.0.as_ref().iter().enumerate() {
+            let (start, end) = match sequence {
+                Sequence::Single(value) => (key(value), key(value)),
+                Sequence::Range(from, to) => {
+                    let (from, to) = (key(from), key(to));
+                    (from.min(to), from.max(to))
+                }
+            };
+
+            if matches!(previous_end, Some(previous_end) if start <= previous_end) {
+                return Err(StrictSequenceSetError::Unordered { index });
+            }
+
+            previous_end = Some(end);
+        }
+
+        Ok(())
+    }
+}
+
+impl Sequence {
+    fn encoded_len(&self) -> usize {
+        match self {
+            Sequence::Single(a) => a.encoded_len(),
+            Sequence::Range(a, b) if a == b => a.encoded_len(),
+            Sequence::Range(a, b) => a.encoded_len() + 1 + b.encoded_len(),
+        }
+    }
 }
 
 impl SeqOrUid {
+    /// Create a [`SeqOrUid::Value`] from `value`.
+    ///
+    /// Returns `None` for `0`, because sequence numbers and UIDs start at `1`.
+    pub fn try_new(value: u32) -> Option<Self> {
+        NonZeroU32::new(value).map(Self::Value)
+    }
+
     pub fn expand(&self, largest: NonZeroU32) -> NonZeroU32 {
         match self {
             SeqOrUid::Value(value) => *value,
             SeqOrUid::Asterisk => largest,
         }
     }
+
+    /// Resolve `*` to the largest number in use in the given context.
+    ///
+    /// Returns `None` when `*` can't be resolved, i.e., when a message sequence number refers to an
+    /// empty mailbox.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::num::NonZeroU32;
+    ///
+    /// use imap_types::sequence::{Largest, SeqOrUid};
+    ///
+    /// // `FETCH *` in a mailbox with 3 messages ...
+    /// let seq = Largest::SequenceNumber { exists: 3 };
+    /// assert_eq!(SeqOrUid::Asterisk.resolve(seq), NonZeroU32::new(3));
+    ///
+    /// // ... and `UID FETCH *` in the same mailbox.
+    /// let uid = Largest::Uid {
+    ///     last: NonZeroU32::new(42),
+    ///     next: NonZeroU32::new(50).unwrap(),
+    /// };
+    /// assert_eq!(SeqOrUid::Asterisk.resolve(uid), NonZeroU32::new(42));
+    /// ```
+    pub fn resolve(&self, largest: Largest) -> Option<NonZeroU32> {
+        match self {
+            SeqOrUid::Value(value) => Some(*value),
+            SeqOrUid::Asterisk => largest.get(),
+        }
+    }
+
+    fn encoded_len(&self) -> usize {
+        match self {
+            SeqOrUid::Value(value) => value.ilog10() as usize + 1,
+            SeqOrUid::Asterisk => 1,
+        }
+    }
+}
+
+/// The "largest number in use" that `*` refers to.
+///
+/// The meaning of `*` depends on whether a sequence set contains message sequence numbers (e.g.,
+/// `FETCH`) or UIDs (e.g., `UID FETCH`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Largest {
+    /// Message sequence numbers: `*` is the number of messages in the mailbox.
+    SequenceNumber {
+        /// Number of messages in the mailbox, i.e., the last `EXISTS` value.
+        exists: u32,
+    },
+    /// UIDs: `*` is the UID of the last message or, if the mailbox is empty, the current UIDNEXT.
+    Uid {
+        /// UID of the last message in the mailbox (`None` if the mailbox is empty).
+        ///
+        /// Note: This is not necessarily UIDNEXT-1 because the last messages may have been expunged.
+        last: Option<NonZeroU32>,
+        /// Current UIDNEXT of the mailbox.
+        next: NonZeroU32,
+    },
+}
+
+impl Largest {
+    /// Get the value of `*` or `None` if `*` can't be resolved (message sequence numbers in an
+    /// empty mailbox).
+    pub fn get(&self) -> Option<NonZeroU32> {
+        match self {
+            Self::SequenceNumber { exists } => NonZeroU32::new(*exists),
+            Self::Uid { last, next } => Some(last.unwrap_or(*next)),
+        }
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -612,6 +796,17 @@ fn cleanup(remaining: VecDeque<(u32, u32)>) -> VecDeque<(u32, u32)> {
     stack
 }
 
+pub mod error {
+    use thiserror::Error;
+
+    /// Error produced by [`SequenceSet::validate_strict`](super::SequenceSet::validate_strict).
+    #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum StrictSequenceSetError {
+        #[error("Sequence at index {index} is out of order or overlaps with its predecessor")]
+        Unordered { index: usize },
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::num::NonZeroU32;
@@ -633,6 +828,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_creation_of_seq_or_uid_from_u32() {
+        assert_eq!(SeqOrUid::try_new(0), None);
+        assert_eq!(
+            SeqOrUid::try_new(1),
+            Some(SeqOrUid::Value(NonZeroU32::new(1).unwrap()))
+        );
+        assert_eq!(
+            SeqOrUid::try_new(u32::MAX),
+            Some(SeqOrUid::Value(NonZeroU32::MAX))
+        );
+
+        assert!(SeqOrUid::try_from(0u32).is_err());
+        assert_eq!(
+            SeqOrUid::try_from(1u32),
+            Ok(SeqOrUid::Value(NonZeroU32::new(1).unwrap()))
+        );
+    }
+
     #[test]
     fn test_creation_of_sequence_from_range() {
         // 1:*
@@ -806,6 +1020,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_strict() {
+        for test in [
+            "1",
+            "*",
+            "1:*",
+            "*:1",
+            "1,2,3",
+            "1:5,7,9:20,21:*",
+            "4294967295,*",
+            "5:1,7",
+            "1,*:3",
+        ] {
+            assert_eq!(
+                SequenceSet::try_from(test).unwrap().validate_strict(),
+                Ok(()),
+                "{test}"
+            );
+        }
+
+        let tests = [
+            ("5:1,3", StrictSequenceSetError::Unordered { index: 1 }),
+            ("1,3:1", StrictSequenceSetError::Unordered { index: 1 }),
+            ("2,1", StrictSequenceSetError::Unordered { index: 1 }),
+            ("1,1", StrictSequenceSetError::Unordered { index: 1 }),
+            ("1:5,5:7", StrictSequenceSetError::Unordered { index: 1 }),
+            (
+                "1:1000000,1:1000000",
+                StrictSequenceSetError::Unordered { index: 1 },
+            ),
+            ("1:*,*", StrictSequenceSetError::Unordered { index: 1 }),
+            ("*,1", StrictSequenceSetError::Unordered { index: 1 }),
+        ];
+
+        for (test, expected) in tests {
+            assert_eq!(
+                SequenceSet::try_from(test).unwrap().validate_strict(),
+                Err(expected),
+                "{test}"
+            );
+        }
+    }
+
     #[test]
     fn test_iteration_over_some_sequence_sets() {
         let tests = vec![
@@ -834,6 +1091,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resolve_asterisk_in_context() {
+        // A mailbox with 3 messages having the UIDs 10, 20, and 30. UIDNEXT is 40.
+        let seq = Largest::SequenceNumber { exists: 3 };
+        let uid = Largest::Uid {
+            last: NonZeroU32::new(30),
+            next: NonZeroU32::new(40).unwrap(),
+        };
+
+        assert_eq!(SeqOrUid::Asterisk.resolve(seq), NonZeroU32::new(3));
+        assert_eq!(SeqOrUid::Asterisk.resolve(uid), NonZeroU32::new(30));
+
+        let value = SeqOrUid::Value(NonZeroU32::new(7).unwrap());
+        assert_eq!(value.resolve(seq), NonZeroU32::new(7));
+        assert_eq!(value.resolve(uid), NonZeroU32::new(7));
+
+        // An empty mailbox with UIDNEXT 40.
+        let seq = Largest::SequenceNumber { exists: 0 };
+        let uid = Largest::Uid {
+            last: None,
+            next: NonZeroU32::new(40).unwrap(),
+        };
+
+        assert_eq!(SeqOrUid::Asterisk.resolve(seq), None);
+        assert_eq!(SeqOrUid::Asterisk.resolve(uid), NonZeroU32::new(40));
+    }
+
     /// See https://github.com/duesee/imap-codec/issues/411
     #[test]
     fn test_issue_411() {
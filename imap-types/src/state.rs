@@ -50,12 +50,19 @@
 //! (7) LOGOUT command, server shutdown, or connection closed
 //! ```
 
+use std::num::NonZeroU32;
+
 #[cfg(feature = "bounded-static")]
 use bounded_static::ToStatic;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::{core::Tag, mailbox::Mailbox};
+use crate::{
+    core::Tag,
+    flag::{Flag, FlagPerm},
+    mailbox::Mailbox,
+    response::{Code, Data, Response},
+};
 
 /// State of the IMAP4rev1 connection.
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
@@ -93,6 +100,118 @@ pub enum State<'a> {
     IdleSelected(Tag<'a>, Mailbox<'a>),
 }
 
+/// Information about the selected mailbox, as announced by the server.
+///
+/// Feed all responses to a `SELECT` or `EXAMINE` command (and later unsolicited responses) into
+/// [`MailboxState::update`].
+///
+/// ```rust
+/// use std::num::NonZeroU32;
+///
+/// use imap_types::{
+///     response::{Code, Data, Response, Status},
+///     state::MailboxState,
+/// };
+///
+/// let mut state = MailboxState::default();
+/// state.update(&Response::Data(Data::Exists(172)));
+/// state.update(&Response::Status(
+///     Status::ok(
+///         None,
+///         Some(Code::Unseen(NonZeroU32::new(12).unwrap())),
+///         "...",
+///     )
+///     .unwrap(),
+/// ));
+///
+/// assert_eq!(state.exists(), Some(172));
+/// assert_eq!(state.first_unseen(), NonZeroU32::new(12));
+/// ```
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MailboxState<'a> {
+    exists: Option<u32>,
+    recent: Option<u32>,
+    flags: Vec<Flag<'a>>,
+    permanent_flags: Option<Vec<FlagPerm<'a>>>,
+    uid_validity: Option<NonZeroU32>,
+    uid_next: Option<NonZeroU32>,
+    first_unseen: Option<NonZeroU32>,
+    read_only: Option<bool>,
+}
+
+impl<'a> MailboxState<'a> {
+    /// Update the state from `response`.
+    ///
+    /// Returns `true` when `response` carried information about the mailbox.
+    pub fn update(&mut self, response: &Response<'a>) -> bool {
+        match response {
+            Response::Data(Data::Exists(exists)) => self.exists = Some(*exists),
+            // Every `EXPUNGE` removes a single message.
+            Response::Data(Data::Expunge(_)) => {
+                self.exists = self.exists.map(|exists| exists.saturating_sub(1))
+            }
+            Response::Data(Data::Recent(recent)) => self.recent = Some(*recent),
+            Response::Data(Data::Flags(flags)) => self.flags = flags.clone(),
+            Response::Status(status) => match status.code() {
+                Some(Code::PermanentFlags(flags)) => self.permanent_flags = Some(flags.clone()),
+                Some(Code::UidValidity(uid_validity)) => self.uid_validity = Some(*uid_validity),
+                Some(Code::UidNext(uid_next)) => self.uid_next = Some(*uid_next),
+                Some(Code::Unseen(unseen)) => self.first_unseen = Some(*unseen),
+                Some(Code::ReadOnly) => self.read_only = Some(true),
+                Some(Code::ReadWrite) => self.read_only = Some(false),
+                _ => return false,
+            },
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// Number of messages in the mailbox (`EXISTS`, decremented by `EXPUNGE`).
+    pub fn exists(&self) -> Option<u32> {
+        self.exists
+    }
+
+    /// Number of messages with the `\Recent` flag (`RECENT`).
+    pub fn recent(&self) -> Option<u32> {
+        self.recent
+    }
+
+    /// Flags defined in the mailbox (`FLAGS`).
+    pub fn flags(&self) -> &[Flag<'a>] {
+        &self.flags
+    }
+
+    /// Flags the client can change permanently (`[PERMANENTFLAGS ...]`).
+    pub fn permanent_flags(&self) -> Option<&[FlagPerm<'a>]> {
+        self.permanent_flags.as_deref()
+    }
+
+    /// Unique identifier validity value (`[UIDVALIDITY ...]`).
+    pub fn uid_validity(&self) -> Option<NonZeroU32> {
+        self.uid_validity
+    }
+
+    /// Predicted next unique identifier (`[UIDNEXT ...]`).
+    pub fn uid_next(&self) -> Option<NonZeroU32> {
+        self.uid_next
+    }
+
+    /// Message sequence number of the first unseen message (`[UNSEEN ...]`).
+    ///
+    /// Clients may use this to jump to the first unread message.
+    pub fn first_unseen(&self) -> Option<NonZeroU32> {
+        self.first_unseen
+    }
+
+    /// Whether the mailbox was selected read-only (`[READ-ONLY]`) or read-write (`[READ-WRITE]`).
+    pub fn read_only(&self) -> Option<bool> {
+        self.read_only
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[cfg(feature = "bounded-static")]
@@ -109,6 +109,19 @@ pub mod indicators {
     }
 }
 
+/// Lowercase `value` into `buffer` without allocating.
+///
+/// Used to match (short) known names case-insensitively. Returns `None` when `value` doesn't fit
+/// into `buffer`, i.e., when it is longer than any known name.
+pub(crate) fn to_ascii_lowercase_in<'b>(value: &str, buffer: &'b mut [u8]) -> Option<&'b str> {
+    let buffer = buffer.get_mut(..value.len())?;
+    buffer.copy_from_slice(value.as_bytes());
+    buffer.make_ascii_lowercase();
+
+    // Lowercasing ASCII bytes keeps UTF-8 valid.
+    std::str::from_utf8(buffer).ok()
+}
+
 pub fn escape_quoted(unescaped: &str) -> Cow<str> {
     let mut escaped = Cow::Borrowed(unescaped);
 